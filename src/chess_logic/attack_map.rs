@@ -0,0 +1,44 @@
+use cgmath::Vector2;
+use std::collections::HashMap;
+
+use crate::chess_logic::Player;
+
+// Squares holding the player's own pieces count as attacked as well, which is what keeps those
+// pieces defended
+#[derive(Clone, Debug)]
+pub struct AttackMap {
+    player: Player,
+    attackers: HashMap<Vector2<u8>, Vec<Vector2<u8>>>,
+}
+
+impl AttackMap {
+    pub fn new(player: Player) -> Self {
+        Self {
+            player,
+            attackers: HashMap::new(),
+        }
+    }
+
+    pub fn add_attack(&mut self, attacker: Vector2<u8>, square: Vector2<u8>) {
+        self.attackers.entry(square).or_default().push(attacker);
+    }
+
+    pub fn player(&self) -> Player {
+        self.player
+    }
+
+    pub fn is_attacked(&self, square: Vector2<u8>) -> bool {
+        self.attackers.contains_key(&square)
+    }
+
+    pub fn attackers(&self, square: Vector2<u8>) -> &[Vector2<u8>] {
+        self.attackers
+            .get(&square)
+            .map(|attackers| attackers.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn attacked_squares(&self) -> impl Iterator<Item = Vector2<u8>> + '_ {
+        self.attackers.keys().copied()
+    }
+}
@@ -4,7 +4,9 @@ use std::{
     ops::{Index, IndexMut},
 };
 
-use crate::chess_logic::{MoveDescriptor, OwnedPiece, Piece, Player};
+use crate::chess_logic::{
+    AttackMap, CastleSide, CastlingRights, MoveDescriptor, OwnedPiece, Piece, Player,
+};
 
 pub const BOARD_SIZE: u8 = 8;
 pub const WHITE_PAWN_START_ROW: u8 = 1;
@@ -49,7 +51,7 @@ impl Iterator for BoardIter {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Board {
     pieces: [Option<OwnedPiece>; BOARD_SIZE.pow(2) as usize],
 }
@@ -102,8 +104,14 @@ impl IndexMut<Vector2<u8>> for Board {
     }
 }
 
+impl Default for Board {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Board {
-    const fn empty() -> Self {
+    pub const fn empty() -> Self {
         Self {
             pieces: [None; BOARD_SIZE.pow(2) as usize],
         }
@@ -147,21 +155,21 @@ impl Board {
             } else if pos == Vector2::new(7, 1) {
                 out[pos] = Some(OwnedPiece::new(Player::White, Piece::Pawn))
             } else if pos == Vector2::new(0, 7) {
-                out[pos] = Some(OwnedPiece::new(Player::Black, Piece::Knight))
+                out[pos] = Some(OwnedPiece::new(Player::Black, Piece::Rook))
             } else if pos == Vector2::new(1, 7) {
-                out[pos] = Some(OwnedPiece::new(Player::Black, Piece::Bishop))
+                out[pos] = Some(OwnedPiece::new(Player::Black, Piece::Knight))
             } else if pos == Vector2::new(2, 7) {
-                out[pos] = Some(OwnedPiece::new(Player::Black, Piece::Queen))
+                out[pos] = Some(OwnedPiece::new(Player::Black, Piece::Bishop))
             } else if pos == Vector2::new(3, 7) {
-                out[pos] = Some(OwnedPiece::new(Player::Black, Piece::King))
+                out[pos] = Some(OwnedPiece::new(Player::Black, Piece::Queen))
             } else if pos == Vector2::new(4, 7) {
-                out[pos] = Some(OwnedPiece::new(Player::Black, Piece::Bishop))
+                out[pos] = Some(OwnedPiece::new(Player::Black, Piece::King))
             } else if pos == Vector2::new(5, 7) {
-                out[pos] = Some(OwnedPiece::new(Player::Black, Piece::Knight))
+                out[pos] = Some(OwnedPiece::new(Player::Black, Piece::Bishop))
             } else if pos == Vector2::new(6, 7) {
-                out[pos] = Some(OwnedPiece::new(Player::Black, Piece::Rook))
+                out[pos] = Some(OwnedPiece::new(Player::Black, Piece::Knight))
             } else if pos == Vector2::new(7, 7) {
-                out[pos] = Some(OwnedPiece::new(Player::Black, Piece::Pawn))
+                out[pos] = Some(OwnedPiece::new(Player::Black, Piece::Rook))
             } else if pos == Vector2::new(0, 6) {
                 out[pos] = Some(OwnedPiece::new(Player::Black, Piece::Pawn))
             } else if pos == Vector2::new(1, 6) {
@@ -184,21 +192,448 @@ impl Board {
         out
     }
 
-    pub fn is_move_valid(&self, move_command: impl Into<MoveDescriptor>) -> bool {
-        // TODO: Implement
-        true
+    pub fn squares() -> impl Iterator<Item = Vector2<u8>> {
+        BoardIter::new()
+    }
+
+    pub fn is_move_valid(
+        &self,
+        move_command: impl Into<MoveDescriptor>,
+        en_passant: Option<Vector2<u8>>,
+    ) -> bool {
+        let move_command = move_command.into();
+
+        let is_promotion_valid = match move_command.promotion {
+            Some(Piece::Pawn | Piece::King) => false,
+            Some(_) => self.is_promotion(move_command),
+            None => !self.is_promotion(move_command),
+        };
+
+        is_promotion_valid
+            && self
+                .reachable_squares(move_command.from, en_passant)
+                .contains(&move_command.to)
+    }
+
+    pub fn is_promotion(&self, move_command: impl Into<MoveDescriptor>) -> bool {
+        let move_command = move_command.into();
+
+        match self[move_command.from] {
+            Some(piece) if piece.piece() == Piece::Pawn => match piece.player() {
+                Player::White => move_command.to.y == WHITE_PAWN_END_ROW,
+                Player::Black => move_command.to.y == BLACK_PAWN_END_ROW,
+            },
+            _ => false,
+        }
+    }
+
+    fn reachable_squares(
+        &self,
+        from: Vector2<u8>,
+        en_passant: Option<Vector2<u8>>,
+    ) -> Vec<Vector2<u8>> {
+        let mut out = Vec::new();
+
+        if let Some(piece) = self[from] {
+            // Pawns may only advance two squares from their starting row
+            let max_steps = match (piece.piece(), piece.player()) {
+                (Piece::Pawn, Player::White) if from.y != WHITE_PAWN_START_ROW => 1,
+                (Piece::Pawn, Player::Black) if from.y != BLACK_PAWN_START_ROW => 1,
+                _ => BOARD_SIZE as usize,
+            };
+
+            for ray in piece.possible_move_dirs() {
+                for dir in ray.into_iter().take(max_steps) {
+                    let Some(to) = offset(from, dir) else {
+                        break;
+                    };
+
+                    if piece.piece() == Piece::Pawn {
+                        // Pawns push straight ahead, but only ever capture diagonally
+                        let is_capture = dir.x != 0;
+
+                        match self[to] {
+                            None if !is_capture || en_passant == Some(to) => out.push(to),
+                            Some(target) if is_capture && target.player() != piece.player() => {
+                                out.push(to)
+                            }
+                            _ => {}
+                        }
+
+                        if self[to].is_some() {
+                            break;
+                        }
+                    } else {
+                        match self[to] {
+                            None => out.push(to),
+                            Some(target) => {
+                                if target.player() != piece.player() {
+                                    out.push(to);
+                                }
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        out
+    }
+
+    pub fn king_position(&self, player: Player) -> Option<Vector2<u8>> {
+        BoardIter::new().find(|&pos| self[pos] == Some(OwnedPiece::new(player, Piece::King)))
+    }
+
+    pub fn is_square_attacked(&self, square: Vector2<u8>, by: Player) -> bool {
+        // Look outwards from the square as every kind of piece would move: running into an
+        // enemy piece of that same kind means it can reach the square
+        [
+            Piece::Pawn,
+            Piece::Bishop,
+            Piece::Knight,
+            Piece::Rook,
+            Piece::Queen,
+            Piece::King,
+        ]
+        .into_iter()
+        .any(|piece| {
+            OwnedPiece::new(-by, piece)
+                .possible_move_dirs()
+                .into_iter()
+                .any(|ray| {
+                    for dir in ray {
+                        if piece == Piece::Pawn && dir.x == 0 {
+                            break;
+                        }
+
+                        let Some(pos) = offset(square, dir) else {
+                            break;
+                        };
+
+                        if let Some(target) = self[pos] {
+                            return target == OwnedPiece::new(by, piece);
+                        }
+                    }
+
+                    false
+                })
+        })
+    }
+
+    pub fn attack_map(&self, player: Player) -> AttackMap {
+        let mut out = AttackMap::new(player);
+
+        for from in self.pieces_of(player) {
+            for square in self.attacked_squares_from(from) {
+                out.add_attack(from, square);
+            }
+        }
+
+        out
+    }
+
+    fn attacked_squares_from(&self, from: Vector2<u8>) -> Vec<Vector2<u8>> {
+        let mut out = Vec::new();
+
+        if let Some(piece) = self[from] {
+            for ray in piece.possible_move_dirs() {
+                for dir in ray {
+                    // Pawn pushes never attack anything
+                    if piece.piece() == Piece::Pawn && dir.x == 0 {
+                        break;
+                    }
+
+                    let Some(to) = offset(from, dir) else {
+                        break;
+                    };
+
+                    out.push(to);
+
+                    if self[to].is_some() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        out
+    }
+
+    pub fn is_in_check(&self, player: Player) -> bool {
+        self.king_position(player)
+            .is_some_and(|king| self.is_square_attacked(king, -player))
+    }
+
+    pub fn leaves_king_in_check(&self, move_command: impl Into<MoveDescriptor>) -> bool {
+        let move_command = move_command.into();
+
+        if let Some(piece) = self[move_command.from] {
+            let mut board = self.clone();
+            board.do_move(move_command);
+
+            board.is_in_check(piece.player())
+        } else {
+            false
+        }
+    }
+
+    pub fn castle_side(&self, move_command: impl Into<MoveDescriptor>) -> Option<CastleSide> {
+        let move_command = move_command.into();
+
+        match self[move_command.from] {
+            Some(piece)
+                if piece.piece() == Piece::King
+                    && move_command.from.y == move_command.to.y
+                    && move_command.from.x.abs_diff(move_command.to.x) == 2 =>
+            {
+                if move_command.to.x > move_command.from.x {
+                    Some(CastleSide::KingSide)
+                } else {
+                    Some(CastleSide::QueenSide)
+                }
+            }
+            _ => None,
+        }
+    }
+
+    pub fn is_castle_valid(&self, player: Player, side: CastleSide) -> bool {
+        let (king_from, rook_from) = (side.king_from(player), side.rook_from(player));
+
+        if self[king_from] != Some(OwnedPiece::new(player, Piece::King))
+            || self[rook_from] != Some(OwnedPiece::new(player, Piece::Rook))
+        {
+            return false;
+        }
+
+        let between = (king_from.x.min(rook_from.x) + 1)..king_from.x.max(rook_from.x);
+        if between
+            .map(|x| Vector2::new(x, king_from.y))
+            .any(|pos| self[pos].is_some())
+        {
+            return false;
+        }
+
+        // The king may not castle out of, through or into check
+        [king_from, side.rook_to(player), side.king_to(player)]
+            .into_iter()
+            .all(|pos| !self.is_square_attacked(pos, -player))
     }
 
     pub fn do_move(&mut self, move_command: impl Into<MoveDescriptor>) {
         let move_command = move_command.into();
 
+        if let Some(side) = self.castle_side(move_command) {
+            let player = self[move_command.from].unwrap().player();
+
+            self[side.rook_to(player)] = self[side.rook_from(player)].take();
+        }
+
+        if self.is_en_passant(move_command) {
+            self[Vector2::new(move_command.to.x, move_command.from.y)] = None;
+        }
+
+        if let (Some(piece), Some(promotion)) = (self[move_command.from], move_command.promotion) {
+            self[move_command.from] = Some(OwnedPiece::new(piece.player(), promotion));
+        }
+
         self[move_command.to] = self[move_command.from];
         self[move_command.from] = None
     }
 
-    pub fn evaluate_bord(&self) -> BoardCondition {
-        // TODO: Implement
-        BoardCondition::Continue
+    // Reverses do_move, given what was captured and the en passant square before the move
+    pub fn undo_move(
+        &mut self,
+        move_command: impl Into<MoveDescriptor>,
+        captured: Option<OwnedPiece>,
+        en_passant: Option<Vector2<u8>>,
+    ) {
+        let move_command = move_command.into();
+
+        let piece = self[move_command.to]
+            .take()
+            .map(|piece| match move_command.promotion {
+                Some(_) => OwnedPiece::new(piece.player(), Piece::Pawn),
+                None => piece,
+            });
+        self[move_command.from] = piece;
+
+        if let Some(side) = self.castle_side(move_command) {
+            let player = piece.unwrap().player();
+
+            self[side.rook_from(player)] = self[side.rook_to(player)].take();
+        }
+
+        let was_en_passant = piece.is_some_and(|piece| piece.piece() == Piece::Pawn)
+            && move_command.from.x != move_command.to.x
+            && en_passant == Some(move_command.to);
+        if was_en_passant {
+            self[Vector2::new(move_command.to.x, move_command.from.y)] = captured;
+        } else {
+            self[move_command.to] = captured;
+        }
+    }
+
+    pub fn is_en_passant(&self, move_command: impl Into<MoveDescriptor>) -> bool {
+        let move_command = move_command.into();
+
+        self[move_command.from].is_some_and(|piece| piece.piece() == Piece::Pawn)
+            && move_command.from.x != move_command.to.x
+            && self[move_command.to].is_none()
+    }
+
+    pub fn can_capture_en_passant(&self, player: Player, en_passant: Vector2<u8>) -> bool {
+        BoardIter::new()
+            .filter(|&pos| self[pos] == Some(OwnedPiece::new(player, Piece::Pawn)))
+            .any(|from| {
+                let move_command = MoveDescriptor {
+                    from,
+                    to: en_passant,
+                    promotion: None,
+                };
+
+                self.is_move_valid(move_command, Some(en_passant))
+                    && !self.leaves_king_in_check(move_command)
+            })
+    }
+
+    pub fn en_passant_target(
+        &self,
+        move_command: impl Into<MoveDescriptor>,
+    ) -> Option<Vector2<u8>> {
+        let move_command = move_command.into();
+
+        if self[move_command.from].is_some_and(|piece| piece.piece() == Piece::Pawn)
+            && move_command.from.y.abs_diff(move_command.to.y) == 2
+        {
+            Some(Vector2::new(
+                move_command.from.x,
+                (move_command.from.y + move_command.to.y) / 2,
+            ))
+        } else {
+            None
+        }
+    }
+
+    pub fn evaluate_bord(&self, turn: Player, en_passant: Option<Vector2<u8>>) -> BoardCondition {
+        if self.has_legal_move(turn, en_passant) {
+            if self.is_dead_position() {
+                BoardCondition::Draw
+            } else {
+                BoardCondition::Continue
+            }
+        } else if self.is_in_check(turn) {
+            BoardCondition::Win(-turn)
+        } else {
+            BoardCondition::Draw
+        }
+    }
+
+    pub fn is_dead_position(&self) -> bool {
+        let mut minor_pieces = Vec::new();
+
+        for pos in BoardIter::new() {
+            if let Some(piece) = self[pos] {
+                match piece.piece() {
+                    Piece::King => {}
+                    Piece::Bishop | Piece::Knight => minor_pieces.push((piece.piece(), pos)),
+                    Piece::Pawn | Piece::Rook | Piece::Queen => return false,
+                }
+            }
+        }
+
+        // A lone minor piece can never mate, and neither can bishops that all share a colour
+        match minor_pieces.as_slice() {
+            [] | [_] => true,
+            [(_, first), ..] => minor_pieces.iter().all(|&(piece, pos)| {
+                piece == Piece::Bishop && square_color(pos) == square_color(*first)
+            }),
+        }
+    }
+
+    fn has_legal_move(&self, player: Player, en_passant: Option<Vector2<u8>>) -> bool {
+        // Castling can be left out, as the king could then also just step aside
+        self.pieces_of(player).any(|from| {
+            !self
+                .legal_moves_from(from, CastlingRights::none(), en_passant)
+                .is_empty()
+        })
+    }
+
+    pub fn legal_moves(
+        &self,
+        player: Player,
+        castling_rights: CastlingRights,
+        en_passant: Option<Vector2<u8>>,
+    ) -> Vec<MoveDescriptor> {
+        self.pieces_of(player)
+            .flat_map(|from| self.legal_moves_from(from, castling_rights, en_passant))
+            .collect()
+    }
+
+    pub fn legal_moves_from(
+        &self,
+        from: Vector2<u8>,
+        castling_rights: CastlingRights,
+        en_passant: Option<Vector2<u8>>,
+    ) -> Vec<MoveDescriptor> {
+        let mut out = Vec::new();
+
+        if let Some(piece) = self[from] {
+            for to in self.reachable_squares(from, en_passant) {
+                let move_command = MoveDescriptor {
+                    from,
+                    to,
+                    promotion: None,
+                };
+
+                if self.leaves_king_in_check(move_command) {
+                    continue;
+                }
+
+                if self.is_promotion(move_command) {
+                    out.extend(
+                        [Piece::Queen, Piece::Rook, Piece::Bishop, Piece::Knight]
+                            .map(|piece| move_command.with_promotion(piece)),
+                    );
+                } else {
+                    out.push(move_command);
+                }
+            }
+
+            for side in [CastleSide::KingSide, CastleSide::QueenSide] {
+                if from == side.king_from(piece.player())
+                    && castling_rights.get(piece.player(), side)
+                    && self.is_castle_valid(piece.player(), side)
+                {
+                    out.push(MoveDescriptor {
+                        from,
+                        to: side.king_to(piece.player()),
+                        promotion: None,
+                    });
+                }
+            }
+        }
+
+        out
+    }
+
+    fn pieces_of(&self, player: Player) -> impl Iterator<Item = Vector2<u8>> + '_ {
+        BoardIter::new().filter(move |&pos| self[pos].is_some_and(|piece| piece.player() == player))
+    }
+}
+
+const fn square_color(pos: Vector2<u8>) -> u8 {
+    (pos.x + pos.y) % 2
+}
+
+fn offset(pos: Vector2<u8>, dir: Vector2<i8>) -> Option<Vector2<u8>> {
+    let (x, y) = (pos.x as i8 + dir.x, pos.y as i8 + dir.y);
+
+    if (0..BOARD_SIZE as i8).contains(&x) && (0..BOARD_SIZE as i8).contains(&y) {
+        Some(Vector2::new(x as u8, y as u8))
+    } else {
+        None
     }
 }
 
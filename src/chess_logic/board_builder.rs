@@ -0,0 +1,205 @@
+use cgmath::Vector2;
+use std::{error::Error, fmt::Display};
+
+use crate::chess_logic::{
+    square_name, Board, CastleSide, CastlingRights, GameState, OwnedPiece, Piece, Player,
+    BOARD_SIZE,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BoardBuilderError {
+    OffBoard(Vector2<u8>),
+    WrongKingCount(Player),
+    PawnOnBackRank(Vector2<u8>),
+    OpponentInCheck,
+    InvalidCastlingRights,
+    InvalidEnPassant,
+}
+
+impl Display for BoardBuilderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BoardBuilderError::OffBoard(pos) => {
+                write!(f, "({}, {}) is not on the board", pos.x, pos.y)
+            }
+            BoardBuilderError::WrongKingCount(player) => {
+                write!(f, "{:?} must have exactly one king", player)
+            }
+            BoardBuilderError::PawnOnBackRank(pos) => {
+                write!(
+                    f,
+                    "There is a pawn on the back rank at {}",
+                    square_name(*pos)
+                )
+            }
+            BoardBuilderError::OpponentInCheck => {
+                write!(f, "The side not to move is in check")
+            }
+            BoardBuilderError::InvalidCastlingRights => {
+                write!(f, "Castling rights don't match the king and rook positions")
+            }
+            BoardBuilderError::InvalidEnPassant => {
+                write!(f, "The en passant square doesn't follow a double pawn push")
+            }
+        }
+    }
+}
+
+impl Error for BoardBuilderError {}
+
+#[derive(Clone, Debug)]
+pub struct BoardBuilder {
+    board: Board,
+    turn: Player,
+    castling_rights: CastlingRights,
+    en_passant: Option<Vector2<u8>>,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+    off_board: Option<Vector2<u8>>,
+}
+
+impl Default for BoardBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BoardBuilder {
+    pub fn new() -> Self {
+        Self {
+            board: Board::empty(),
+            turn: Player::White,
+            castling_rights: CastlingRights::none(),
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            off_board: None,
+        }
+    }
+
+    pub fn place(mut self, pos: impl Into<Vector2<u8>>, piece: OwnedPiece) -> Self {
+        let pos = pos.into();
+        if self.check_on_board(pos) {
+            self.board[pos] = Some(piece);
+        }
+        self
+    }
+
+    pub fn remove(mut self, pos: impl Into<Vector2<u8>>) -> Self {
+        let pos = pos.into();
+        if self.check_on_board(pos) {
+            self.board[pos] = None;
+        }
+        self
+    }
+
+    // The first square off the board is remembered and reported by build
+    fn check_on_board(&mut self, pos: Vector2<u8>) -> bool {
+        let on_board = pos.x < BOARD_SIZE && pos.y < BOARD_SIZE;
+        if !on_board && self.off_board.is_none() {
+            self.off_board = Some(pos);
+        }
+
+        on_board
+    }
+
+    pub fn turn(mut self, player: Player) -> Self {
+        self.turn = player;
+        self
+    }
+
+    pub fn castling_rights(mut self, castling_rights: CastlingRights) -> Self {
+        self.castling_rights = castling_rights;
+        self
+    }
+
+    pub fn en_passant(mut self, en_passant: Option<Vector2<u8>>) -> Self {
+        self.en_passant = en_passant;
+        self
+    }
+
+    pub fn halfmove_clock(mut self, halfmove_clock: u32) -> Self {
+        self.halfmove_clock = halfmove_clock;
+        self
+    }
+
+    pub fn fullmove_number(mut self, fullmove_number: u32) -> Self {
+        self.fullmove_number = fullmove_number;
+        self
+    }
+
+    pub fn build(self) -> Result<GameState, BoardBuilderError> {
+        self.validate()?;
+
+        Ok(GameState::from_parts(
+            self.board,
+            self.turn,
+            self.castling_rights,
+            self.en_passant,
+            self.halfmove_clock,
+            self.fullmove_number,
+        ))
+    }
+
+    fn validate(&self) -> Result<(), BoardBuilderError> {
+        if let Some(pos) = self.off_board {
+            return Err(BoardBuilderError::OffBoard(pos));
+        }
+
+        for player in [Player::White, Player::Black] {
+            let king = OwnedPiece::new(player, Piece::King);
+
+            if Board::squares()
+                .filter(|&pos| self.board[pos] == Some(king))
+                .count()
+                != 1
+            {
+                return Err(BoardBuilderError::WrongKingCount(player));
+            }
+        }
+
+        if let Some(pos) = Board::squares().find(|&pos| {
+            (pos.y == 0 || pos.y == BOARD_SIZE - 1)
+                && self.board[pos].is_some_and(|piece| piece.piece() == Piece::Pawn)
+        }) {
+            return Err(BoardBuilderError::PawnOnBackRank(pos));
+        }
+
+        if self.board.is_in_check(-self.turn) {
+            return Err(BoardBuilderError::OpponentInCheck);
+        }
+
+        for player in [Player::White, Player::Black] {
+            for side in [CastleSide::KingSide, CastleSide::QueenSide] {
+                if self.castling_rights.get(player, side)
+                    && (self.board[side.king_from(player)]
+                        != Some(OwnedPiece::new(player, Piece::King))
+                        || self.board[side.rook_from(player)]
+                            != Some(OwnedPiece::new(player, Piece::Rook)))
+                {
+                    return Err(BoardBuilderError::InvalidCastlingRights);
+                }
+            }
+        }
+
+        if let Some(en_passant) = self.en_passant {
+            // The pawn that just moved stands in front of the square, coming from behind it
+            let (target_row, pawn_row, origin_row) = match self.turn {
+                Player::White => (5, 4, 6),
+                Player::Black => (2, 3, 1),
+            };
+
+            if en_passant.x >= BOARD_SIZE
+                || en_passant.y != target_row
+                || self.board[en_passant].is_some()
+                || self.board[Vector2::new(en_passant.x, origin_row)].is_some()
+                || self.board[Vector2::new(en_passant.x, pawn_row)]
+                    != Some(OwnedPiece::new(-self.turn, Piece::Pawn))
+            {
+                return Err(BoardBuilderError::InvalidEnPassant);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use cgmath::Vector2;
+
+use crate::chess_logic::{MoveDescriptor, Player};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CastleSide {
+    KingSide,
+    QueenSide,
+}
+
+impl CastleSide {
+    pub fn king_from(&self, player: Player) -> Vector2<u8> {
+        Vector2::new(4, home_row(player))
+    }
+
+    pub fn king_to(&self, player: Player) -> Vector2<u8> {
+        match self {
+            CastleSide::KingSide => Vector2::new(6, home_row(player)),
+            CastleSide::QueenSide => Vector2::new(2, home_row(player)),
+        }
+    }
+
+    pub fn rook_from(&self, player: Player) -> Vector2<u8> {
+        match self {
+            CastleSide::KingSide => Vector2::new(7, home_row(player)),
+            CastleSide::QueenSide => Vector2::new(0, home_row(player)),
+        }
+    }
+
+    pub fn rook_to(&self, player: Player) -> Vector2<u8> {
+        match self {
+            CastleSide::KingSide => Vector2::new(5, home_row(player)),
+            CastleSide::QueenSide => Vector2::new(3, home_row(player)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CastlingRights {
+    pub white_king_side: bool,
+    pub white_queen_side: bool,
+    pub black_king_side: bool,
+    pub black_queen_side: bool,
+}
+
+impl Default for CastlingRights {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl CastlingRights {
+    pub const fn all() -> Self {
+        Self {
+            white_king_side: true,
+            white_queen_side: true,
+            black_king_side: true,
+            black_queen_side: true,
+        }
+    }
+
+    pub const fn none() -> Self {
+        Self {
+            white_king_side: false,
+            white_queen_side: false,
+            black_king_side: false,
+            black_queen_side: false,
+        }
+    }
+
+    pub fn get(&self, player: Player, side: CastleSide) -> bool {
+        match (player, side) {
+            (Player::White, CastleSide::KingSide) => self.white_king_side,
+            (Player::White, CastleSide::QueenSide) => self.white_queen_side,
+            (Player::Black, CastleSide::KingSide) => self.black_king_side,
+            (Player::Black, CastleSide::QueenSide) => self.black_queen_side,
+        }
+    }
+
+    pub fn set(&mut self, player: Player, side: CastleSide, value: bool) {
+        match (player, side) {
+            (Player::White, CastleSide::KingSide) => self.white_king_side = value,
+            (Player::White, CastleSide::QueenSide) => self.white_queen_side = value,
+            (Player::Black, CastleSide::KingSide) => self.black_king_side = value,
+            (Player::Black, CastleSide::QueenSide) => self.black_queen_side = value,
+        }
+    }
+
+    pub fn update(&mut self, move_command: impl Into<MoveDescriptor>) {
+        let move_command = move_command.into();
+
+        // Anything moving from or onto a king's or rook's starting square means that piece has
+        // either moved or been captured
+        for player in [Player::White, Player::Black] {
+            for side in [CastleSide::KingSide, CastleSide::QueenSide] {
+                let touched = |square| move_command.from == square || move_command.to == square;
+
+                if touched(side.king_from(player)) || touched(side.rook_from(player)) {
+                    self.set(player, side, false);
+                }
+            }
+        }
+    }
+}
+
+const fn home_row(player: Player) -> u8 {
+    match player {
+        Player::White => 0,
+        Player::Black => 7,
+    }
+}
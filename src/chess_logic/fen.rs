@@ -0,0 +1,231 @@
+use cgmath::Vector2;
+use std::{error::Error, fmt::Display};
+
+use crate::chess_logic::{
+    parse_square, square_name, BoardBuilder, BoardBuilderError, CastlingRights, GameState,
+    OwnedPiece, Piece, Player, BOARD_SIZE,
+};
+
+pub const STARTING_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FenError {
+    WrongFieldCount(usize),
+    PiecePlacement,
+    SideToMove,
+    CastlingRights,
+    EnPassant,
+    HalfmoveClock,
+    FullmoveNumber,
+    InvalidPosition(BoardBuilderError),
+}
+
+impl Display for FenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FenError::WrongFieldCount(count) => {
+                write!(f, "Expected 4 or 6 fields, found {}", count)
+            }
+            FenError::PiecePlacement => write!(f, "Invalid piece placement field"),
+            FenError::SideToMove => write!(f, "Invalid side to move field"),
+            FenError::CastlingRights => write!(f, "Invalid castling rights field"),
+            FenError::EnPassant => write!(f, "Invalid en passant field"),
+            FenError::HalfmoveClock => write!(f, "Invalid halfmove clock field"),
+            FenError::FullmoveNumber => write!(f, "Invalid fullmove number field"),
+            FenError::InvalidPosition(error) => write!(f, "Invalid position: {}", error),
+        }
+    }
+}
+
+impl Error for FenError {}
+
+impl GameState {
+    pub fn from_fen(fen: &str) -> Result<Self, FenError> {
+        let fields = fen.split_whitespace().collect::<Vec<_>>();
+
+        // The move counters are often left out, e.g. in EPD
+        let (placement, side_to_move, castling, en_passant, halfmove_clock, fullmove_number) =
+            match fields.as_slice() {
+                [placement, side_to_move, castling, en_passant] => {
+                    (placement, side_to_move, castling, en_passant, &"0", &"1")
+                }
+                [placement, side_to_move, castling, en_passant, halfmove_clock, fullmove_number] => {
+                    (
+                        placement,
+                        side_to_move,
+                        castling,
+                        en_passant,
+                        halfmove_clock,
+                        fullmove_number,
+                    )
+                }
+                _ => return Err(FenError::WrongFieldCount(fields.len())),
+            };
+
+        let mut builder = parse_placement(placement)?;
+
+        builder = builder.turn(match *side_to_move {
+            "w" => Player::White,
+            "b" => Player::Black,
+            _ => return Err(FenError::SideToMove),
+        });
+
+        builder = builder.castling_rights(parse_castling_rights(castling)?);
+
+        builder = builder.en_passant(match *en_passant {
+            "-" => None,
+            square => Some(parse_square(square).map_err(|_| FenError::EnPassant)?),
+        });
+
+        builder = builder
+            .halfmove_clock(
+                halfmove_clock
+                    .parse()
+                    .map_err(|_| FenError::HalfmoveClock)?,
+            )
+            .fullmove_number(
+                fullmove_number
+                    .parse()
+                    .ok()
+                    .filter(|&number| number > 0)
+                    .ok_or(FenError::FullmoveNumber)?,
+            );
+
+        builder.build().map_err(|error| match error {
+            BoardBuilderError::InvalidCastlingRights => FenError::CastlingRights,
+            BoardBuilderError::InvalidEnPassant => FenError::EnPassant,
+            error => FenError::InvalidPosition(error),
+        })
+    }
+
+    pub fn to_fen(&self) -> String {
+        let mut placement = String::new();
+
+        for y in (0..BOARD_SIZE).rev() {
+            let mut empty = 0;
+
+            for x in 0..BOARD_SIZE {
+                if let Some(piece) = self.board()[Vector2::new(x, y)] {
+                    if empty > 0 {
+                        placement.push_str(&empty.to_string());
+                        empty = 0;
+                    }
+                    placement.push(piece_to_char(piece));
+                } else {
+                    empty += 1;
+                }
+            }
+
+            if empty > 0 {
+                placement.push_str(&empty.to_string());
+            }
+            if y > 0 {
+                placement.push('/');
+            }
+        }
+
+        let castling_rights = self.castling_rights();
+        let mut castling = [
+            (castling_rights.white_king_side, 'K'),
+            (castling_rights.white_queen_side, 'Q'),
+            (castling_rights.black_king_side, 'k'),
+            (castling_rights.black_queen_side, 'q'),
+        ]
+        .into_iter()
+        .filter_map(|(allowed, char)| allowed.then_some(char))
+        .collect::<String>();
+        if castling.is_empty() {
+            castling.push('-');
+        }
+
+        format!(
+            "{} {} {} {} {} {}",
+            placement,
+            match self.turn() {
+                Player::White => "w",
+                Player::Black => "b",
+            },
+            castling,
+            self.en_passant().map_or("-".to_string(), square_name),
+            self.halfmove_clock(),
+            self.fullmove_number()
+        )
+    }
+}
+
+fn parse_placement(placement: &str) -> Result<BoardBuilder, FenError> {
+    let ranks = placement.split('/').collect::<Vec<_>>();
+    if ranks.len() != BOARD_SIZE as usize {
+        return Err(FenError::PiecePlacement);
+    }
+
+    let mut builder = BoardBuilder::new();
+
+    for (rank, y) in ranks.into_iter().zip((0..BOARD_SIZE).rev()) {
+        let mut x = 0;
+
+        for char in rank.chars() {
+            if let Some(skip) = char.to_digit(10).filter(|skip| (1..=8).contains(skip)) {
+                x += skip as u8;
+            } else {
+                let piece =
+                    Piece::from_char(char.to_ascii_uppercase()).ok_or(FenError::PiecePlacement)?;
+                let player = if char.is_ascii_uppercase() {
+                    Player::White
+                } else {
+                    Player::Black
+                };
+
+                if x >= BOARD_SIZE {
+                    return Err(FenError::PiecePlacement);
+                }
+                builder = builder.place((x, y), OwnedPiece::new(player, piece));
+                x += 1;
+            }
+
+            if x > BOARD_SIZE {
+                return Err(FenError::PiecePlacement);
+            }
+        }
+
+        if x != BOARD_SIZE {
+            return Err(FenError::PiecePlacement);
+        }
+    }
+
+    Ok(builder)
+}
+
+fn parse_castling_rights(castling: &str) -> Result<CastlingRights, FenError> {
+    let mut out = CastlingRights::none();
+
+    if castling == "-" {
+        return Ok(out);
+    }
+
+    for char in castling.chars() {
+        let right = match char {
+            'K' => &mut out.white_king_side,
+            'Q' => &mut out.white_queen_side,
+            'k' => &mut out.black_king_side,
+            'q' => &mut out.black_queen_side,
+            _ => return Err(FenError::CastlingRights),
+        };
+
+        if *right {
+            return Err(FenError::CastlingRights);
+        }
+        *right = true;
+    }
+
+    Ok(out)
+}
+
+fn piece_to_char(piece: OwnedPiece) -> char {
+    let char = piece.piece().to_string().chars().next().unwrap();
+
+    match piece.player() {
+        Player::White => char,
+        Player::Black => char.to_ascii_lowercase(),
+    }
+}
@@ -1,24 +1,83 @@
+use cgmath::Vector2;
 use std::{fmt::Display, ops::Neg};
 
-use crate::chess_logic::{Board, BoardCondition, MoveDescriptor, Player};
+use crate::chess_logic::{
+    zobrist, Board, BoardCondition, CastlingRights, MoveDescriptor, OwnedPiece, Piece, Player,
+};
+
+pub(crate) const FIFTY_MOVE_RULE: u32 = 100;
+const SEVENTY_FIVE_MOVE_RULE: u32 = 150;
+const THREEFOLD_REPETITION: usize = 3;
+const FIVEFOLD_REPETITION: usize = 5;
 
 #[derive(Clone, Copy, Debug)]
 pub enum StateUpdate {
     Continue,
-    InvalidMove,
+    InvalidMove(InvalidMoveReason),
     Draw,
     Win(Player),
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InvalidMoveReason {
+    EmptySquare,
+    OpponentsPiece,
+    IllegalMove,
+    KingInCheck,
+    CastlingNotAllowed,
+    MissingPromotion,
+}
+
+impl Display for InvalidMoveReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                InvalidMoveReason::EmptySquare => "There is no piece on that square",
+                InvalidMoveReason::OpponentsPiece => "That piece belongs to your opponent",
+                InvalidMoveReason::IllegalMove => "That piece can't move there",
+                InvalidMoveReason::KingInCheck => "That move would leave your king in check",
+                InvalidMoveReason::CastlingNotAllowed => "You can't castle there right now",
+                InvalidMoveReason::MissingPromotion => "Choose a piece to promote to",
+            }
+        )
+    }
+}
+
+// A played move together with everything needed to take it back
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct HistoryEntry {
+    pub move_command: MoveDescriptor,
+    pub captured: Option<OwnedPiece>,
+    pub castling_rights: CastlingRights,
+    pub en_passant: Option<Vector2<u8>>,
+    pub halfmove_clock: u32,
+}
+
 #[derive(Clone, Debug)]
 pub struct GameState {
     board: Board,
     turn: Player,
+    castling_rights: CastlingRights,
+    en_passant: Option<Vector2<u8>>,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+    zobrist_key: u64,
+    position_history: Vec<u64>,
+    claim_based_draws: bool,
+    start_fen: String,
+    history: Vec<HistoryEntry>,
+    redo_stack: Vec<MoveDescriptor>,
+    outcome: Option<StateUpdate>,
 }
 
 impl Display for GameState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "{}'s turn", self.turn)?;
+        if self.in_check() {
+            writeln!(f, "{} is in check", self.turn)?;
+        }
         writeln!(f, "{}", self.board)?;
 
         Ok(())
@@ -27,27 +86,320 @@ impl Display for GameState {
 
 impl GameState {
     pub fn new(starting_player: impl Into<Player>) -> Self {
-        Self {
-            board: Board::new(),
-            turn: starting_player.into(),
-        }
+        Self::from_parts(
+            Board::new(),
+            starting_player.into(),
+            CastlingRights::all(),
+            None,
+            0,
+            1,
+        )
+    }
+
+    pub(crate) fn from_parts(
+        board: Board,
+        turn: Player,
+        castling_rights: CastlingRights,
+        en_passant: Option<Vector2<u8>>,
+        halfmove_clock: u32,
+        fullmove_number: u32,
+    ) -> Self {
+        let mut out = Self {
+            board,
+            turn,
+            castling_rights,
+            en_passant,
+            halfmove_clock,
+            fullmove_number,
+            zobrist_key: 0,
+            position_history: Vec::new(),
+            claim_based_draws: false,
+            start_fen: String::new(),
+            history: Vec::new(),
+            redo_stack: Vec::new(),
+            outcome: None,
+        };
+        out.zobrist_key = out.board.zobrist_key()
+            ^ zobrist::turn_key(out.turn)
+            ^ zobrist::castling_key(out.castling_rights)
+            ^ out.en_passant_key();
+        out.position_history.push(out.zobrist_key);
+        out.start_fen = out.to_fen();
+
+        out
+    }
+
+    pub fn set_claim_based_draws(&mut self, claim_based_draws: bool) {
+        self.claim_based_draws = claim_based_draws;
     }
 
     pub fn play(&mut self, move_command: impl Into<MoveDescriptor>) -> StateUpdate {
         let move_command = move_command.into();
 
-        if self.board.is_move_valid(move_command) {
-            self.board.do_move(move_command);
-            match self.board.evaluate_bord() {
-                BoardCondition::Win(player) => StateUpdate::Win(player),
-                BoardCondition::Draw => StateUpdate::Draw,
-                BoardCondition::Continue => {
-                    self.turn = self.turn.neg();
+        match self.board[move_command.from] {
+            None => return StateUpdate::InvalidMove(InvalidMoveReason::EmptySquare),
+            Some(piece) if piece.player() != self.turn => {
+                return StateUpdate::InvalidMove(InvalidMoveReason::OpponentsPiece)
+            }
+            Some(_) => {}
+        }
+
+        if let Some(side) = self.board.castle_side(move_command) {
+            if !self.castling_rights.get(self.turn, side)
+                || !self.board.is_castle_valid(self.turn, side)
+            {
+                return StateUpdate::InvalidMove(InvalidMoveReason::CastlingNotAllowed);
+            }
+        } else if !self.board.is_move_valid(move_command, self.en_passant) {
+            // Would this have been fine if only a promotion piece was given?
+            return if move_command.promotion.is_none()
+                && self
+                    .board
+                    .is_move_valid(move_command.with_promotion(Piece::Queen), self.en_passant)
+            {
+                StateUpdate::InvalidMove(InvalidMoveReason::MissingPromotion)
+            } else {
+                StateUpdate::InvalidMove(InvalidMoveReason::IllegalMove)
+            };
+        } else if self.board.leaves_king_in_check(move_command) {
+            return StateUpdate::InvalidMove(InvalidMoveReason::KingInCheck);
+        }
+
+        self.apply_move(move_command);
+
+        // Replaying the next undone move keeps the rest of the redo stack around
+        if self.redo_stack.last() == Some(&move_command) {
+            self.redo_stack.pop();
+        } else {
+            self.redo_stack.clear();
+        }
+
+        let state_update = self.evaluate();
+        if let StateUpdate::Win(_) | StateUpdate::Draw = state_update {
+            self.outcome = Some(state_update);
+        }
+
+        state_update
+    }
+
+    fn evaluate(&self) -> StateUpdate {
+        match self.board.evaluate_bord(self.turn, self.en_passant) {
+            BoardCondition::Win(player) => StateUpdate::Win(player),
+            BoardCondition::Draw => StateUpdate::Draw,
+            BoardCondition::Continue => {
+                // Without claims the draw is declared as soon as it could have been claimed
+                let (move_limit, repetition_limit) = if self.claim_based_draws {
+                    (SEVENTY_FIVE_MOVE_RULE, FIVEFOLD_REPETITION)
+                } else {
+                    (FIFTY_MOVE_RULE, THREEFOLD_REPETITION)
+                };
+
+                if self.halfmove_clock >= move_limit || self.repetitions() >= repetition_limit {
+                    StateUpdate::Draw
+                } else {
                     StateUpdate::Continue
                 }
             }
+        }
+    }
+
+    // Plays an already validated move without looking at how the game continues
+    pub(crate) fn apply_move(&mut self, move_command: MoveDescriptor) {
+        let captured = if self.board.is_en_passant(move_command) {
+            Some(OwnedPiece::new(self.turn.neg(), Piece::Pawn))
+        } else {
+            self.board[move_command.to]
+        };
+        self.history.push(HistoryEntry {
+            move_command,
+            captured,
+            castling_rights: self.castling_rights,
+            en_passant: self.en_passant,
+            halfmove_clock: self.halfmove_clock,
+        });
+
+        // Take everything the move can change out of the key, and put it back in afterwards
+        let touched_squares = self.touched_squares(move_command);
+        let mut zobrist_key = self.zobrist_key
+            ^ zobrist::turn_key(self.turn)
+            ^ zobrist::castling_key(self.castling_rights)
+            ^ self.en_passant_key()
+            ^ self.pieces_key(&touched_squares);
+
+        let is_irreversible = self.board[move_command.to].is_some()
+            || self.board[move_command.from].is_some_and(|piece| piece.piece() == Piece::Pawn);
+        self.halfmove_clock = if is_irreversible {
+            0
         } else {
-            StateUpdate::InvalidMove
+            self.halfmove_clock + 1
+        };
+
+        self.en_passant = self.board.en_passant_target(move_command);
+        self.board.do_move(move_command);
+        self.castling_rights.update(move_command);
+        if self.turn == Player::Black {
+            self.fullmove_number += 1;
         }
+        self.turn = self.turn.neg();
+
+        zobrist_key ^= zobrist::turn_key(self.turn)
+            ^ zobrist::castling_key(self.castling_rights)
+            ^ self.en_passant_key()
+            ^ self.pieces_key(&touched_squares);
+        self.zobrist_key = zobrist_key;
+        self.position_history.push(zobrist_key);
+    }
+
+    fn touched_squares(&self, move_command: MoveDescriptor) -> Vec<Vector2<u8>> {
+        let mut out = vec![move_command.from, move_command.to];
+
+        if let Some(side) = self.board.castle_side(move_command) {
+            out.push(side.rook_from(self.turn));
+            out.push(side.rook_to(self.turn));
+        } else if self.board.is_en_passant(move_command) {
+            out.push(Vector2::new(move_command.to.x, move_command.from.y));
+        }
+
+        out
+    }
+
+    fn pieces_key(&self, squares: &[Vector2<u8>]) -> u64 {
+        squares
+            .iter()
+            .filter_map(|&square| self.board[square].map(|piece| zobrist::piece_key(piece, square)))
+            .fold(0, |out, key| out ^ key)
+    }
+
+    // An en passant square only sets a position apart if the capture can actually be made
+    fn en_passant_key(&self) -> u64 {
+        zobrist::en_passant_key(
+            self.en_passant
+                .filter(|&target| self.board.can_capture_en_passant(self.turn, target)),
+        )
+    }
+
+    // Takes back the last move applied, without touching the redo stack
+    pub(crate) fn revert_move(&mut self) -> Option<MoveDescriptor> {
+        let entry = self.history.pop()?;
+
+        self.turn = self.turn.neg();
+        if self.turn == Player::Black {
+            self.fullmove_number -= 1;
+        }
+        self.board
+            .undo_move(entry.move_command, entry.captured, entry.en_passant);
+        self.castling_rights = entry.castling_rights;
+        self.en_passant = entry.en_passant;
+        self.halfmove_clock = entry.halfmove_clock;
+        self.position_history.pop();
+        self.zobrist_key = *self
+            .position_history
+            .last()
+            .expect("the starting position is always in the history");
+
+        Some(entry.move_command)
+    }
+
+    pub fn undo(&mut self) -> Option<MoveDescriptor> {
+        let move_command = self.revert_move()?;
+        self.redo_stack.push(move_command);
+        self.outcome = None;
+
+        Some(move_command)
+    }
+
+    pub fn redo(&mut self) -> Option<StateUpdate> {
+        let move_command = *self.redo_stack.last()?;
+
+        Some(self.play(move_command))
+    }
+
+    pub fn moves(&self) -> impl Iterator<Item = MoveDescriptor> + '_ {
+        self.history.iter().map(|entry| entry.move_command)
+    }
+
+    pub fn history(&self) -> &[HistoryEntry] {
+        &self.history
+    }
+
+    pub fn can_claim_draw(&self) -> bool {
+        self.halfmove_clock >= FIFTY_MOVE_RULE || self.repetitions() >= THREEFOLD_REPETITION
+    }
+
+    pub fn claim_draw(&mut self) -> StateUpdate {
+        if self.can_claim_draw() {
+            self.outcome = Some(StateUpdate::Draw);
+            StateUpdate::Draw
+        } else {
+            StateUpdate::Continue
+        }
+    }
+
+    pub fn start_fen(&self) -> &str {
+        &self.start_fen
+    }
+
+    pub fn outcome(&self) -> Option<StateUpdate> {
+        self.outcome
+    }
+
+    pub(crate) fn set_outcome(&mut self, outcome: StateUpdate) {
+        self.outcome = Some(outcome);
+    }
+
+    pub fn halfmove_clock(&self) -> u32 {
+        self.halfmove_clock
+    }
+
+    pub fn fullmove_number(&self) -> u32 {
+        self.fullmove_number
+    }
+
+    pub fn repetitions(&self) -> usize {
+        self.position_history
+            .iter()
+            .filter(|&&key| key == self.zobrist_key)
+            .count()
+    }
+
+    pub fn zobrist_key(&self) -> u64 {
+        self.zobrist_key
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn turn(&self) -> Player {
+        self.turn
+    }
+
+    pub fn legal_moves(&self) -> Vec<MoveDescriptor> {
+        self.board
+            .legal_moves(self.turn, self.castling_rights, self.en_passant)
+    }
+
+    pub fn legal_moves_from(&self, square: impl Into<Vector2<u8>>) -> Vec<MoveDescriptor> {
+        let square = square.into();
+
+        match self.board[square] {
+            Some(piece) if piece.player() == self.turn => {
+                self.board
+                    .legal_moves_from(square, self.castling_rights, self.en_passant)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn castling_rights(&self) -> CastlingRights {
+        self.castling_rights
+    }
+
+    pub fn en_passant(&self) -> Option<Vector2<u8>> {
+        self.en_passant
+    }
+
+    pub fn in_check(&self) -> bool {
+        self.board.is_in_check(self.turn)
     }
 }
@@ -1,11 +1,28 @@
+mod attack_map;
 mod board;
+mod board_builder;
+mod castling;
+mod fen;
 mod game_state;
 mod move_descriptor;
+mod perft;
+mod pgn;
 mod piece;
 mod player;
+mod san;
+mod zobrist;
 
-pub use board::{Board, BoardCondition, BOARD_SIZE};
-pub use game_state::{GameState, StateUpdate};
-pub use move_descriptor::MoveDescriptor;
+pub use attack_map::AttackMap;
+pub use board::{
+    Board, BoardCondition, BLACK_PAWN_END_ROW, BLACK_PAWN_START_ROW, BOARD_SIZE,
+    WHITE_PAWN_END_ROW, WHITE_PAWN_START_ROW,
+};
+pub use board_builder::{BoardBuilder, BoardBuilderError};
+pub use castling::{CastleSide, CastlingRights};
+pub use fen::{FenError, STARTING_FEN};
+pub(crate) use game_state::FIFTY_MOVE_RULE;
+pub use game_state::{GameState, HistoryEntry, InvalidMoveReason, StateUpdate};
+pub use move_descriptor::{parse_square, square_name, MoveDescriptor, MoveParseError};
+pub use pgn::{read_pgn, result_token, PgnError, PgnGame, PgnTags};
 pub use piece::{OwnedPiece, Piece};
 pub use player::Player;
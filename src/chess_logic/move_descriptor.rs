@@ -1,13 +1,13 @@
 use cgmath::Vector2;
+use std::{error::Error, fmt::Display};
 
-use crate::chess_logic::BOARD_SIZE;
-
-// TODO: Check castle, ompasant
+use crate::chess_logic::{Piece, BOARD_SIZE};
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct MoveDescriptor {
     pub from: Vector2<u8>,
     pub to: Vector2<u8>,
+    pub promotion: Option<Piece>,
 }
 
 impl MoveDescriptor {
@@ -15,62 +15,128 @@ impl MoveDescriptor {
         let (from, to) = (from.into(), to.into());
 
         if fits_on_board(from, to) {
-            Some(Self { from, to })
+            Some(Self {
+                from,
+                to,
+                promotion: None,
+            })
         } else {
             None
         }
     }
+
+    pub fn with_promotion(self, piece: Piece) -> Self {
+        Self {
+            promotion: Some(piece),
+            ..self
+        }
+    }
+}
+
+impl Display for MoveDescriptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", square_name(self.from), square_name(self.to))?;
+
+        if let Some(piece) = self.promotion {
+            write!(f, "{}", piece.to_string().to_lowercase())?;
+        }
+
+        Ok(())
+    }
 }
 
 impl TryFrom<String> for MoveDescriptor {
-    type Error = ();
+    type Error = MoveParseError;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        let value = value.trim();
-
-        if value.len() == 4 {
-            let (mut from, mut to) = (Vector2::new(0, 0), Vector2::new(0, 0));
-
-            for (index, char) in value.char_indices() {
-                if (index % 2 == 0 && char.is_alphabetic()) | (index % 2 != 0 && char.is_numeric())
-                {
-                    if let Ok(num) = char_to_num(char) {
-                        match index {
-                            0 => from.x = num,
-                            1 => from.y = num,
-                            2 => to.x = num,
-                            3 => to.y = num,
-                            _ => unreachable!(),
-                        }
-                    } else {
-                        return Err(());
+        let value = value.trim().chars().collect::<Vec<_>>();
+
+        if value.len() == 4 || value.len() == 5 {
+            let from = Vector2::new(file_to_num(value[0])?, rank_to_num(value[1])?);
+            let to = Vector2::new(file_to_num(value[2])?, rank_to_num(value[3])?);
+
+            let move_command = MoveDescriptor::new(from, to).ok_or(MoveParseError::OffBoard)?;
+
+            match value.get(4) {
+                Some(&char) => match Piece::from_char(char.to_ascii_uppercase()) {
+                    Some(piece @ (Piece::Queen | Piece::Rook | Piece::Bishop | Piece::Knight)) => {
+                        Ok(move_command.with_promotion(piece))
                     }
-                } else {
-                    return Err(());
-                }
+                    _ => Err(MoveParseError::BadPromotion(char)),
+                },
+                None => Ok(move_command),
             }
-
-            Ok(MoveDescriptor::new(from, to).unwrap())
         } else {
-            Err(())
+            Err(MoveParseError::WrongLength(value.len()))
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MoveParseError {
+    WrongLength(usize),
+    BadFile(char),
+    BadRank(char),
+    BadPiece(char),
+    BadPromotion(char),
+    OffBoard,
+    NoMatchingMove,
+    Ambiguous,
+}
+
+impl Display for MoveParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MoveParseError::WrongLength(length) => write!(
+                f,
+                "Expected a move like e2e4 or Nf3, but got {} characters",
+                length
+            ),
+            MoveParseError::BadFile(char) => write!(f, "'{}' is not a file letter", char),
+            MoveParseError::BadRank(char) => write!(f, "'{}' is not a rank digit", char),
+            MoveParseError::BadPiece(char) => write!(f, "'{}' is not a piece", char),
+            MoveParseError::BadPromotion(char) => {
+                write!(f, "Can't promote to '{}', use q, r, b or n", char)
+            }
+            MoveParseError::OffBoard => write!(f, "That square is not on the board"),
+            MoveParseError::NoMatchingMove => write!(f, "No legal move matches that"),
+            MoveParseError::Ambiguous => {
+                write!(f, "More than one move matches that, say which piece moves")
+            }
         }
     }
 }
 
+impl Error for MoveParseError {}
+
+pub fn square_name(pos: Vector2<u8>) -> String {
+    format!("{}{}", (b'a' + pos.x) as char, pos.y + 1)
+}
+
+pub fn parse_square(value: &str) -> Result<Vector2<u8>, MoveParseError> {
+    let chars = value.chars().collect::<Vec<_>>();
+
+    match chars.as_slice() {
+        &[file, rank] => Ok(Vector2::new(file_to_num(file)?, rank_to_num(rank)?)),
+        chars => Err(MoveParseError::WrongLength(chars.len())),
+    }
+}
+
 const fn fits_on_board(from: Vector2<u8>, to: Vector2<u8>) -> bool {
     from.x < BOARD_SIZE && from.y < BOARD_SIZE && to.x < BOARD_SIZE && to.y < BOARD_SIZE
 }
 
-const fn char_to_num(char: char) -> Result<u8, ()> {
+const fn file_to_num(char: char) -> Result<u8, MoveParseError> {
+    match char {
+        'a'..='h' => Ok(char as u8 - b'a'),
+        _ => Err(MoveParseError::BadFile(char)),
+    }
+}
+
+const fn rank_to_num(char: char) -> Result<u8, MoveParseError> {
     match char {
-        'a' | '1' => Ok(0),
-        'b' | '2' => Ok(1),
-        'c' | '3' => Ok(2),
-        'd' | '4' => Ok(3),
-        'e' | '5' => Ok(4),
-        'f' | '6' => Ok(5),
-        'g' | '7' => Ok(6),
-        'h' | '8' => Ok(7),
-        _ => Err(()),
+        '1'..='8' => Ok(char as u8 - b'1'),
+        '0' | '9' => Err(MoveParseError::OffBoard),
+        _ => Err(MoveParseError::BadRank(char)),
     }
 }
@@ -0,0 +1,42 @@
+use crate::chess_logic::{GameState, MoveDescriptor};
+
+impl GameState {
+    pub fn perft(&self, depth: u32) -> u64 {
+        self.clone().count_nodes(depth)
+    }
+
+    pub fn divide(&self, depth: u32) -> Vec<(MoveDescriptor, u64)> {
+        if depth == 0 {
+            return Vec::new();
+        }
+
+        let mut game_state = self.clone();
+        self.legal_moves()
+            .into_iter()
+            .map(|move_command| {
+                game_state.apply_move(move_command);
+                let nodes = game_state.count_nodes(depth - 1);
+                game_state.revert_move();
+
+                (move_command, nodes)
+            })
+            .collect()
+    }
+
+    fn count_nodes(&mut self, depth: u32) -> u64 {
+        match depth {
+            0 => 1,
+            1 => self.legal_moves().len() as u64,
+            _ => {
+                let mut nodes = 0;
+                for move_command in self.legal_moves() {
+                    self.apply_move(move_command);
+                    nodes += self.count_nodes(depth - 1);
+                    self.revert_move();
+                }
+
+                nodes
+            }
+        }
+    }
+}
@@ -0,0 +1,387 @@
+use std::{error::Error, fmt::Display, iter::Peekable, mem, str::Chars};
+
+use crate::chess_logic::{
+    FenError, GameState, MoveDescriptor, MoveParseError, Player, StateUpdate, STARTING_FEN,
+};
+
+const MAX_LINE_LENGTH: usize = 80;
+
+#[derive(Clone, Debug)]
+pub struct PgnTags {
+    pub event: String,
+    pub site: String,
+    pub date: String,
+    pub round: String,
+    pub white: String,
+    pub black: String,
+    pub time_control: Option<String>,
+    pub white_engine: Option<String>,
+    pub black_engine: Option<String>,
+}
+
+impl Default for PgnTags {
+    fn default() -> Self {
+        Self {
+            event: "?".to_string(),
+            site: "?".to_string(),
+            date: "????.??.??".to_string(),
+            round: "?".to_string(),
+            white: "?".to_string(),
+            black: "?".to_string(),
+            time_control: None,
+            white_engine: None,
+            black_engine: None,
+        }
+    }
+}
+
+impl GameState {
+    pub fn to_pgn(&self, tags: &PgnTags) -> String {
+        let result = result_token(self.outcome());
+
+        let mut out = String::new();
+        for (name, value) in [
+            ("Event", Some(&tags.event)),
+            ("Site", Some(&tags.site)),
+            ("Date", Some(&tags.date)),
+            ("Round", Some(&tags.round)),
+            ("White", Some(&tags.white)),
+            ("Black", Some(&tags.black)),
+            ("Result", Some(&result.to_string())),
+            ("TimeControl", tags.time_control.as_ref()),
+            ("WhiteEngine", tags.white_engine.as_ref()),
+            ("BlackEngine", tags.black_engine.as_ref()),
+        ] {
+            if let Some(value) = value {
+                out.push_str(&format!("[{} \"{}\"]\n", name, escape(value)));
+            }
+        }
+
+        if self.start_fen() != STARTING_FEN {
+            out.push_str("[SetUp \"1\"]\n");
+            out.push_str(&format!("[FEN \"{}\"]\n", self.start_fen()));
+        }
+        out.push('\n');
+
+        let mut tokens = Vec::new();
+        let mut game_state = GameState::from_fen(self.start_fen())
+            .expect("the starting position was valid when the game began");
+
+        for (index, move_command) in self.moves().enumerate() {
+            match game_state.turn() {
+                Player::White => tokens.push(format!("{}.", game_state.fullmove_number())),
+                Player::Black if index == 0 => {
+                    tokens.push(format!("{}...", game_state.fullmove_number()))
+                }
+                Player::Black => {}
+            }
+
+            tokens.push(game_state.to_san(move_command));
+            game_state.apply_move(move_command);
+        }
+        tokens.push(result.to_string());
+
+        let mut line_length = 0;
+        for token in tokens {
+            if line_length > 0 && line_length + 1 + token.len() > MAX_LINE_LENGTH {
+                out.push('\n');
+                line_length = 0;
+            } else if line_length > 0 {
+                out.push(' ');
+                line_length += 1;
+            }
+
+            line_length += token.len();
+            out.push_str(&token);
+        }
+        out.push('\n');
+
+        out
+    }
+}
+
+pub fn result_token(outcome: Option<StateUpdate>) -> &'static str {
+    match outcome {
+        Some(StateUpdate::Win(Player::White)) => "1-0",
+        Some(StateUpdate::Win(Player::Black)) => "0-1",
+        Some(StateUpdate::Draw) => "1/2-1/2",
+        _ => "*",
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[derive(Clone, Debug)]
+pub struct PgnGame {
+    pub tags: PgnTags,
+    pub moves: Vec<MoveDescriptor>,
+    pub result: String,
+    pub game_state: GameState,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum PgnError {
+    InvalidTag,
+    UnterminatedComment,
+    UnterminatedVariation,
+    InvalidFen {
+        game: usize,
+        error: FenError,
+    },
+    IllegalMove {
+        game: usize,
+        ply: usize,
+        san: String,
+        error: Option<MoveParseError>,
+    },
+}
+
+impl Display for PgnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PgnError::InvalidTag => write!(f, "Invalid tag pair"),
+            PgnError::UnterminatedComment => write!(f, "Comment is never closed"),
+            PgnError::UnterminatedVariation => write!(f, "Variation is never closed"),
+            PgnError::InvalidFen { game, error } => {
+                write!(f, "Game {}: invalid FEN tag: {}", game, error)
+            }
+            PgnError::IllegalMove {
+                game,
+                ply,
+                san,
+                error,
+            } => {
+                write!(f, "Game {}, ply {}: illegal move {}", game, ply, san)?;
+                if let Some(error) = error {
+                    write!(f, " ({})", error)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for PgnError {}
+
+enum Token {
+    Tag(String, String),
+    San(String),
+    Result(String),
+}
+
+pub fn read_pgn(pgn: &str) -> Result<Vec<PgnGame>, PgnError> {
+    let mut out = Vec::new();
+    let mut tags = Vec::new();
+    let mut sans = Vec::new();
+
+    for token in tokenize(pgn)? {
+        match token {
+            // A tag after some movetext means the previous game lacked a result
+            Token::Tag(name, value) => {
+                if !sans.is_empty() {
+                    out.push(replay(
+                        out.len() + 1,
+                        mem::take(&mut tags),
+                        mem::take(&mut sans),
+                        "*",
+                    )?);
+                }
+                tags.push((name, value));
+            }
+            Token::San(san) => sans.push(san),
+            Token::Result(result) => {
+                out.push(replay(
+                    out.len() + 1,
+                    mem::take(&mut tags),
+                    mem::take(&mut sans),
+                    &result,
+                )?);
+            }
+        }
+    }
+
+    if !tags.is_empty() || !sans.is_empty() {
+        out.push(replay(out.len() + 1, tags, sans, "*")?);
+    }
+
+    Ok(out)
+}
+
+fn replay(
+    game: usize,
+    tags: Vec<(String, String)>,
+    sans: Vec<String>,
+    result: &str,
+) -> Result<PgnGame, PgnError> {
+    let tag = |name: &str| {
+        tags.iter()
+            .find(|(tag_name, _)| tag_name == name)
+            .map(|(_, value)| value.clone())
+    };
+
+    let mut game_state = match tag("FEN") {
+        Some(fen) => {
+            GameState::from_fen(&fen).map_err(|error| PgnError::InvalidFen { game, error })?
+        }
+        None => GameState::new(Player::White),
+    };
+    game_state.set_claim_based_draws(true);
+
+    let default_tags = PgnTags::default();
+    let pgn_tags = PgnTags {
+        event: tag("Event").unwrap_or(default_tags.event),
+        site: tag("Site").unwrap_or(default_tags.site),
+        date: tag("Date").unwrap_or(default_tags.date),
+        round: tag("Round").unwrap_or(default_tags.round),
+        white: tag("White").unwrap_or(default_tags.white),
+        black: tag("Black").unwrap_or(default_tags.black),
+        time_control: tag("TimeControl"),
+        white_engine: tag("WhiteEngine"),
+        black_engine: tag("BlackEngine"),
+    };
+
+    let mut moves = Vec::new();
+    for (index, san) in sans.into_iter().enumerate() {
+        let illegal_move = |error| PgnError::IllegalMove {
+            game,
+            ply: index + 1,
+            san: san.clone(),
+            error,
+        };
+
+        let move_command = game_state
+            .parse_san(&san)
+            .map_err(|error| illegal_move(Some(error)))?;
+        if let StateUpdate::InvalidMove(_) = game_state.play(move_command) {
+            return Err(illegal_move(None));
+        }
+
+        moves.push(move_command);
+    }
+
+    // Resignations and the like only show up in the result
+    if game_state.outcome().is_none() {
+        match result {
+            "1-0" => game_state.set_outcome(StateUpdate::Win(Player::White)),
+            "0-1" => game_state.set_outcome(StateUpdate::Win(Player::Black)),
+            "1/2-1/2" => game_state.set_outcome(StateUpdate::Draw),
+            _ => {}
+        }
+    }
+
+    Ok(PgnGame {
+        tags: pgn_tags,
+        moves,
+        result: result.to_string(),
+        game_state,
+    })
+}
+
+fn tokenize(pgn: &str) -> Result<Vec<Token>, PgnError> {
+    let mut out = Vec::new();
+    let mut chars = pgn.chars().peekable();
+    let mut at_line_start = true;
+
+    while let Some(char) = chars.next() {
+        match char {
+            '%' if at_line_start => skip_line(&mut chars),
+            ';' => skip_line(&mut chars),
+            '{' => skip_comment(&mut chars)?,
+            '(' => {
+                let mut depth = 1;
+
+                while depth > 0 {
+                    match chars.next() {
+                        Some('(') => depth += 1,
+                        Some(')') => depth -= 1,
+                        Some('{') => skip_comment(&mut chars)?,
+                        Some(';') => skip_line(&mut chars),
+                        Some(_) => {}
+                        None => return Err(PgnError::UnterminatedVariation),
+                    }
+                }
+            }
+            '[' => out.push(read_tag(&mut chars)?),
+            '$' => while chars.next_if(char::is_ascii_digit).is_some() {},
+            char if char.is_whitespace() => {}
+            char => {
+                let mut symbol = char.to_string();
+                while let Some(char) =
+                    chars.next_if(|char| !char.is_whitespace() && !"{}()[];$".contains(*char))
+                {
+                    symbol.push(char);
+                }
+
+                match symbol.as_str() {
+                    "1-0" | "0-1" | "1/2-1/2" | "*" => out.push(Token::Result(symbol)),
+                    _ => {
+                        let san = strip_move_number(&symbol);
+                        if !san.is_empty() {
+                            out.push(Token::San(san.to_string()));
+                        }
+                    }
+                }
+            }
+        }
+
+        at_line_start = char == '\n';
+    }
+
+    Ok(out)
+}
+
+fn read_tag(chars: &mut Peekable<Chars>) -> Result<Token, PgnError> {
+    let mut name = String::new();
+    while let Some(char) = chars.next_if(|char| *char != '"' && *char != ']') {
+        name.push(char);
+    }
+    let name = name.trim().to_string();
+
+    if name.is_empty() || chars.next() != Some('"') {
+        return Err(PgnError::InvalidTag);
+    }
+
+    let mut value = String::new();
+    loop {
+        match chars.next() {
+            Some('\\') => value.push(chars.next().ok_or(PgnError::InvalidTag)?),
+            Some('"') => break,
+            Some(char) => value.push(char),
+            None => return Err(PgnError::InvalidTag),
+        }
+    }
+
+    while chars.next_if(|char| char.is_whitespace()).is_some() {}
+    if chars.next() != Some(']') {
+        return Err(PgnError::InvalidTag);
+    }
+
+    Ok(Token::Tag(name, value))
+}
+
+fn skip_comment(chars: &mut Peekable<Chars>) -> Result<(), PgnError> {
+    for char in chars.by_ref() {
+        if char == '}' {
+            return Ok(());
+        }
+    }
+
+    Err(PgnError::UnterminatedComment)
+}
+
+fn skip_line(chars: &mut Peekable<Chars>) {
+    while chars.next_if(|char| *char != '\n').is_some() {}
+}
+
+// Turns "12.e4" or "12..." into "e4" and "", but leaves castling like "0-0" alone
+fn strip_move_number(symbol: &str) -> &str {
+    match symbol.split_once('.') {
+        Some((number, rest)) if number.chars().all(|char| char.is_ascii_digit()) => {
+            rest.trim_start_matches('.')
+        }
+        _ => symbol,
+    }
+}
@@ -32,6 +32,18 @@ impl Display for Piece {
 }
 
 impl Piece {
+    pub fn from_char(char: char) -> Option<Self> {
+        match char {
+            'P' => Some(Piece::Pawn),
+            'B' => Some(Piece::Bishop),
+            'N' => Some(Piece::Knight),
+            'R' => Some(Piece::Rook),
+            'Q' => Some(Piece::Queen),
+            'K' => Some(Piece::King),
+            _ => None,
+        }
+    }
+
     pub fn value(&self) -> f32 {
         match self {
             Piece::Pawn => PARAMETERS.pawn_value,
@@ -44,7 +56,7 @@ impl Piece {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct OwnedPiece {
     player: Player,
     piece: Piece,
@@ -55,12 +67,12 @@ impl OwnedPiece {
         match self.piece {
             Piece::Pawn => match self.player {
                 Player::White => vec![
-                    vec![Vector2::new(0, 1)],
+                    vec![Vector2::new(0, 1), Vector2::new(0, 2)],
                     vec![Vector2::new(-1, 1)],
                     vec![Vector2::new(1, 1)],
                 ],
                 Player::Black => vec![
-                    vec![Vector2::new(0, -1)],
+                    vec![Vector2::new(0, -1), Vector2::new(0, -2)],
                     vec![Vector2::new(-1, -1)],
                     vec![Vector2::new(1, -1)],
                 ],
@@ -233,7 +245,7 @@ impl OwnedPiece {
                 vec![Vector2::new(0, -1)],
                 vec![Vector2::new(-1, -1)],
                 vec![Vector2::new(-1, 0)],
-                vec![Vector2::new(1, 1)],
+                vec![Vector2::new(-1, 1)],
             ],
         }
     }
@@ -243,6 +255,14 @@ impl OwnedPiece {
     pub fn new(player: Player, piece: Piece) -> Self {
         Self { player, piece }
     }
+
+    pub fn player(&self) -> Player {
+        self.player
+    }
+
+    pub fn piece(&self) -> Piece {
+        self.piece
+    }
 }
 
 impl Display for OwnedPiece {
@@ -1,7 +1,7 @@
 use ansi_term::Color::{Green, Red};
 use std::{fmt::Display, ops::Neg};
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum Player {
     White,
     Black,
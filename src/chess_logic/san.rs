@@ -0,0 +1,167 @@
+use cgmath::Vector2;
+
+use crate::chess_logic::{
+    parse_square, square_name, CastleSide, GameState, MoveDescriptor, MoveParseError, Piece,
+};
+
+impl GameState {
+    // The move is expected to be legal in this position
+    pub fn to_san(&self, move_command: MoveDescriptor) -> String {
+        let board = self.board();
+        let mut out = String::new();
+
+        match board.castle_side(move_command) {
+            Some(CastleSide::KingSide) => out.push_str("O-O"),
+            Some(CastleSide::QueenSide) => out.push_str("O-O-O"),
+            None => {
+                let piece = board[move_command.from].map_or(Piece::Pawn, |piece| piece.piece());
+                let is_capture =
+                    board[move_command.to].is_some() || board.is_en_passant(move_command);
+
+                if piece == Piece::Pawn {
+                    if is_capture {
+                        out.push(file_char(move_command.from));
+                    }
+                } else {
+                    out.push_str(&piece.to_string());
+                    out.push_str(&self.disambiguation(move_command, piece));
+                }
+
+                if is_capture {
+                    out.push('x');
+                }
+                out.push_str(&square_name(move_command.to));
+
+                if let Some(promotion) = move_command.promotion {
+                    out.push('=');
+                    out.push_str(&promotion.to_string());
+                }
+            }
+        }
+
+        let mut game_state = self.clone();
+        game_state.apply_move(move_command);
+        if game_state.in_check() {
+            out.push(if game_state.legal_moves().is_empty() {
+                '#'
+            } else {
+                '+'
+            });
+        }
+
+        out
+    }
+
+    pub fn parse_san(&self, san: &str) -> Result<MoveDescriptor, MoveParseError> {
+        let san = san.trim().trim_end_matches(['+', '#', '!', '?']);
+
+        if let Some(side) = match san {
+            "O-O" | "0-0" => Some(CastleSide::KingSide),
+            "O-O-O" | "0-0-0" => Some(CastleSide::QueenSide),
+            _ => None,
+        } {
+            let king_from = side.king_from(self.turn());
+
+            return self
+                .legal_moves_from(king_from)
+                .into_iter()
+                .find(|move_command| move_command.to == side.king_to(self.turn()))
+                .ok_or(MoveParseError::NoMatchingMove);
+        }
+
+        let (san, promotion) = match san.split_once('=') {
+            Some((san, promotion)) => (san, Some(parse_promotion(promotion)?)),
+            None => match san.char_indices().last() {
+                // Also accept promotions written without the '=', like e8Q
+                Some((index, char)) if char.is_ascii_uppercase() => {
+                    (&san[..index], Some(parse_promotion(&san[index..])?))
+                }
+                _ => (san, None),
+            },
+        };
+
+        let (piece, san) = match san.chars().next() {
+            Some(char) if char.is_ascii_uppercase() => (
+                Piece::from_char(char).ok_or(MoveParseError::BadPiece(char))?,
+                &san[char.len_utf8()..],
+            ),
+            _ => (Piece::Pawn, san),
+        };
+
+        let san = san.replace(['x', ':', '-'], "");
+        if san.len() < 2 || !san.is_ascii() {
+            return Err(MoveParseError::WrongLength(san.chars().count()));
+        }
+        let (disambiguation, to) = san.split_at(san.len() - 2);
+        let to = parse_square(to)?;
+
+        let (mut from_file, mut from_rank) = (None, None);
+        for char in disambiguation.chars() {
+            match char {
+                'a'..='h' if from_file.is_none() => from_file = Some(char as u8 - b'a'),
+                '1'..='8' if from_rank.is_none() => from_rank = Some(char as u8 - b'1'),
+                '0'..='9' => return Err(MoveParseError::BadRank(char)),
+                _ => return Err(MoveParseError::BadFile(char)),
+            }
+        }
+
+        // A pawn without a source file moves straight ahead, so captures need the file
+        if piece == Piece::Pawn && from_file.is_none() {
+            from_file = Some(to.x);
+        }
+
+        let mut candidates = self.legal_moves().into_iter().filter(|move_command| {
+            move_command.to == to
+                && move_command.promotion == promotion
+                && self.board()[move_command.from].is_some_and(|owned| owned.piece() == piece)
+                && from_file.is_none_or(|x| move_command.from.x == x)
+                && from_rank.is_none_or(|y| move_command.from.y == y)
+        });
+
+        match (candidates.next(), candidates.next()) {
+            (Some(move_command), None) => Ok(move_command),
+            (Some(_), Some(_)) => Err(MoveParseError::Ambiguous),
+            (None, _) => Err(MoveParseError::NoMatchingMove),
+        }
+    }
+
+    fn disambiguation(&self, move_command: MoveDescriptor, piece: Piece) -> String {
+        let others = self
+            .legal_moves()
+            .into_iter()
+            .filter(|other| {
+                other.to == move_command.to
+                    && other.from != move_command.from
+                    && self.board()[other.from].is_some_and(|owned| owned.piece() == piece)
+            })
+            .map(|other| other.from)
+            .collect::<Vec<_>>();
+
+        if others.is_empty() {
+            String::new()
+        } else if others.iter().all(|from| from.x != move_command.from.x) {
+            file_char(move_command.from).to_string()
+        } else if others.iter().all(|from| from.y != move_command.from.y) {
+            (move_command.from.y + 1).to_string()
+        } else {
+            square_name(move_command.from)
+        }
+    }
+}
+
+fn parse_promotion(value: &str) -> Result<Piece, MoveParseError> {
+    let mut chars = value.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(char), None) => match Piece::from_char(char.to_ascii_uppercase()) {
+            Some(piece @ (Piece::Queen | Piece::Rook | Piece::Bishop | Piece::Knight)) => Ok(piece),
+            _ => Err(MoveParseError::BadPromotion(char)),
+        },
+        (Some(char), Some(_)) => Err(MoveParseError::BadPromotion(char)),
+        (None, _) => Err(MoveParseError::WrongLength(0)),
+    }
+}
+
+fn file_char(pos: Vector2<u8>) -> char {
+    (b'a' + pos.x) as char
+}
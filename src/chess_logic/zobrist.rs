@@ -0,0 +1,102 @@
+use cgmath::Vector2;
+
+use crate::chess_logic::{Board, CastlingRights, OwnedPiece, Player, BOARD_SIZE};
+
+const SQUARE_COUNT: usize = (BOARD_SIZE as usize) * (BOARD_SIZE as usize);
+const PIECE_KEY_COUNT: usize = 2 * 6 * SQUARE_COUNT;
+
+struct ZobristKeys {
+    pieces: [u64; PIECE_KEY_COUNT],
+    black_to_move: u64,
+    castling: [u64; 4],
+    en_passant: [u64; BOARD_SIZE as usize],
+}
+
+static KEYS: ZobristKeys = generate_keys();
+
+// Seeded splitmix64, so keys are the same on every run and need no rand dependency
+const fn generate_keys() -> ZobristKeys {
+    let mut state = 0x3243_F6A8_885A_308D;
+
+    let mut pieces = [0; PIECE_KEY_COUNT];
+    let mut i = 0;
+    while i < PIECE_KEY_COUNT {
+        pieces[i] = next_key(&mut state);
+        i += 1;
+    }
+
+    let black_to_move = next_key(&mut state);
+
+    let mut castling = [0; 4];
+    let mut i = 0;
+    while i < castling.len() {
+        castling[i] = next_key(&mut state);
+        i += 1;
+    }
+
+    let mut en_passant = [0; BOARD_SIZE as usize];
+    let mut i = 0;
+    while i < en_passant.len() {
+        en_passant[i] = next_key(&mut state);
+        i += 1;
+    }
+
+    ZobristKeys {
+        pieces,
+        black_to_move,
+        castling,
+        en_passant,
+    }
+}
+
+const fn next_key(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+    let mut key = *state;
+    key = (key ^ (key >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    key = (key ^ (key >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    key ^ (key >> 31)
+}
+
+pub(crate) fn piece_key(piece: OwnedPiece, square: Vector2<u8>) -> u64 {
+    let player_index = match piece.player() {
+        Player::White => 0,
+        Player::Black => 1,
+    };
+    let square_index = (square.y * BOARD_SIZE + square.x) as usize;
+
+    KEYS.pieces[(player_index * 6 + piece.piece() as usize) * SQUARE_COUNT + square_index]
+}
+
+pub(crate) fn turn_key(turn: Player) -> u64 {
+    match turn {
+        Player::White => 0,
+        Player::Black => KEYS.black_to_move,
+    }
+}
+
+pub(crate) fn castling_key(castling_rights: CastlingRights) -> u64 {
+    [
+        castling_rights.white_king_side,
+        castling_rights.white_queen_side,
+        castling_rights.black_king_side,
+        castling_rights.black_queen_side,
+    ]
+    .iter()
+    .zip(KEYS.castling)
+    .filter(|(&right, _)| right)
+    .fold(0, |out, (_, key)| out ^ key)
+}
+
+pub(crate) fn en_passant_key(en_passant: Option<Vector2<u8>>) -> u64 {
+    en_passant.map_or(0, |square| KEYS.en_passant[square.x as usize])
+}
+
+impl Board {
+    // Covers only the pieces, GameState mixes in the rest
+    pub fn zobrist_key(&self) -> u64 {
+        Board::squares()
+            .filter_map(|square| self[square].map(|piece| piece_key(piece, square)))
+            .fold(0, |out, key| out ^ key)
+    }
+}
@@ -0,0 +1,3 @@
+pub mod chess_logic;
+pub mod parameters;
+pub mod search;
@@ -1,14 +1,118 @@
-use std::io::{self, Write};
+use std::{
+    env, fs,
+    io::{self, Write},
+    time::{Duration, Instant},
+};
 
-use crate::chess_logic::{GameState, MoveDescriptor, Player, StateUpdate};
-
-mod chess_logic;
-mod parameters;
+use chess_bot::{
+    chess_logic::{
+        parse_square, square_name, GameState, InvalidMoveReason, MoveDescriptor, PgnTags, Piece,
+        Player, StateUpdate, STARTING_FEN,
+    },
+    parameters::PARAMETERS,
+    search::{Search, MAX_DEPTH},
+};
 
 fn main() -> io::Result<()> {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+
+    match args.first().map(String::as_str) {
+        Some("perft") => run_perft(&args[1..], false),
+        Some("divide") => run_perft(&args[1..], true),
+        _ => play(&args),
+    }
+}
+
+fn run_perft(args: &[String], divide: bool) -> io::Result<()> {
+    let invalid_input = |message: String| io::Error::new(io::ErrorKind::InvalidInput, message);
+
+    let depth = args
+        .first()
+        .and_then(|depth| depth.parse::<u32>().ok())
+        // perft(0) counts the root but divide(0) has no moves to list, so neither allows it
+        .filter(|&depth| depth > 0)
+        .ok_or_else(|| invalid_input("Usage: perft|divide <depth> [fen]".to_string()))?;
+    let fen = if args.len() > 1 {
+        args[1..].join(" ")
+    } else {
+        STARTING_FEN.to_string()
+    };
+    let game_state = GameState::from_fen(&fen).map_err(|error| invalid_input(error.to_string()))?;
+
+    let start = Instant::now();
+    let nodes = if divide {
+        let divide = game_state.divide(depth);
+        for (move_command, nodes) in &divide {
+            println!("{move_command}: {nodes}");
+        }
+
+        divide.iter().map(|(_, nodes)| nodes).sum()
+    } else {
+        game_state.perft(depth)
+    };
+
+    println!();
+    println!("Nodes searched: {nodes}");
+    println!("Time: {:?}", start.elapsed());
+
+    Ok(())
+}
+
+fn play(args: &[String]) -> io::Result<()> {
+    let invalid_input = |message: &str| io::Error::new(io::ErrorKind::InvalidInput, message);
+
+    let pgn_path = option_value(args, "--pgn");
+    let bot = match option_value(args, "--play-as") {
+        Some("white") => Some(Player::Black),
+        Some("black") => Some(Player::White),
+        Some(_) => return Err(invalid_input("Usage: --play-as white|black")),
+        None => None,
+    };
+    let depth = match option_value(args, "--depth") {
+        Some(depth) => Some(
+            depth
+                .parse::<u32>()
+                .ok()
+                .filter(|depth| (1..=MAX_DEPTH).contains(depth))
+                .ok_or_else(|| invalid_input(&format!("Usage: --depth <1-{MAX_DEPTH}>")))?,
+        ),
+        None => None,
+    };
+    let search_time = match option_value(args, "--time") {
+        Some(seconds) => seconds
+            .parse::<f32>()
+            .ok()
+            .and_then(|seconds| Duration::try_from_secs_f32(seconds).ok())
+            .ok_or_else(|| invalid_input("Usage: --time <seconds>"))?,
+        None => Duration::try_from_secs_f32(PARAMETERS.search_time).unwrap_or_default(),
+    };
+
     let mut game_state = GameState::new(Player::White);
+    game_state.set_claim_based_draws(true);
+    let mut search = Search::new();
+    let mut message = None;
 
     loop {
+        if bot == Some(game_state.turn()) {
+            let result = match depth {
+                Some(depth) => search.best_move(&game_state, depth),
+                None => search.best_move_within(&game_state, search_time),
+            };
+
+            if let Some(result) = result {
+                message = Some(format!(
+                    "Bot played {} (depth {}, {} nodes)",
+                    game_state.to_san(result.best_move),
+                    result.depth,
+                    result.nodes
+                ));
+
+                if report_game_over(game_state.play(result.best_move)) {
+                    break;
+                }
+            }
+        }
+
         let mut user_input = String::new();
         let stdin = io::stdin();
 
@@ -19,27 +123,144 @@ fn main() -> io::Result<()> {
         print!("{}[2J", 27 as char);
         println!("{game_state:}");
 
+        if let Some(message) = message.take() {
+            println!("{message}");
+        }
+
         print!("Your move: ");
         io::stdout().flush()?;
 
-        stdin.read_line(&mut user_input)?;
+        if stdin.read_line(&mut user_input)? == 0 {
+            break;
+        }
 
-        if let Ok(move_command) = MoveDescriptor::try_from(user_input) {
-            match game_state.play(move_command) {
-                StateUpdate::Draw => {
-                    println!("Draw");
-                    break;
-                }
-                StateUpdate::Win(player) => {
-                    println!("{} won", player);
-                    break;
+        if user_input.trim().is_empty() {
+            continue;
+        } else if user_input.trim() == "draw" {
+            if report_game_over(game_state.claim_draw()) {
+                break;
+            } else {
+                message = Some("You can't claim a draw right now".to_string());
+            }
+        } else if user_input.trim() == "undo" {
+            if game_state.undo().is_none() {
+                message = Some("There is no move to take back".to_string());
+            } else if bot == Some(game_state.turn()) {
+                // Take back the bot's reply as well
+                game_state.undo();
+            }
+        } else if user_input.trim() == "redo" {
+            match game_state.redo() {
+                Some(state_update) if report_game_over(state_update) => break,
+                Some(_) => {}
+                None => message = Some("There is no move to redo".to_string()),
+            }
+        } else if user_input.trim() == "fen" {
+            message = Some(game_state.to_fen());
+        } else if let Some(fen) = user_input.trim().strip_prefix("fen ") {
+            match GameState::from_fen(fen) {
+                Ok(new_game_state) => {
+                    game_state = new_game_state;
+                    game_state.set_claim_based_draws(true);
                 }
-                StateUpdate::InvalidMove | StateUpdate::Continue => {}
+                Err(error) => message = Some(error.to_string()),
             }
+        } else if let Some(square) = user_input.trim().strip_prefix("moves ") {
+            message = Some(match parse_square(square.trim()) {
+                Ok(square) => {
+                    let moves = game_state
+                        .legal_moves_from(square)
+                        .iter()
+                        .map(|move_command| move_command.to_string())
+                        .collect::<Vec<_>>();
+
+                    if moves.is_empty() {
+                        format!("{} has no legal moves", square_name(square))
+                    } else {
+                        moves.join(" ")
+                    }
+                }
+                Err(error) => error.to_string(),
+            });
         } else {
-            continue;
+            // Input shaped like e2e4 gets the coordinate error, anything else is read as SAN
+            let parsed = if is_coordinate_shaped(user_input.trim()) {
+                MoveDescriptor::try_from(user_input.clone())
+            } else {
+                game_state.parse_san(&user_input)
+            };
+            let move_command = match parsed {
+                Ok(move_command) => move_command,
+                Err(error) => {
+                    message = Some(error.to_string());
+                    continue;
+                }
+            };
+
+            let mut state_update = game_state.play(move_command);
+
+            if let StateUpdate::InvalidMove(InvalidMoveReason::MissingPromotion) = state_update {
+                state_update = game_state.play(move_command.with_promotion(ask_promotion()?));
+            }
+
+            if let StateUpdate::InvalidMove(reason) = state_update {
+                message = Some(reason.to_string());
+            } else if report_game_over(state_update) {
+                break;
+            }
         }
     }
 
+    if let Some(pgn_path) = pgn_path {
+        fs::write(pgn_path, game_state.to_pgn(&PgnTags::default()))?;
+    }
+
     Ok(())
 }
+
+// Prints the result if the game has ended, and says whether it did
+fn report_game_over(state_update: StateUpdate) -> bool {
+    match state_update {
+        StateUpdate::Draw => println!("Draw"),
+        StateUpdate::Win(player) => println!("{} won", player),
+        StateUpdate::Continue | StateUpdate::InvalidMove(_) => return false,
+    }
+
+    true
+}
+
+fn is_coordinate_shaped(input: &str) -> bool {
+    let chars = input.chars().collect::<Vec<_>>();
+
+    (chars.len() == 4 || chars.len() == 5)
+        && ('a'..='h').contains(&chars[0])
+        && chars[1].is_ascii_digit()
+}
+
+fn ask_promotion() -> io::Result<Piece> {
+    loop {
+        let mut user_input = String::new();
+
+        print!("Promote to (q, r, b, n): ");
+        io::stdout().flush()?;
+
+        io::stdin().read_line(&mut user_input)?;
+
+        if let Some(piece @ (Piece::Queen | Piece::Rook | Piece::Bishop | Piece::Knight)) =
+            user_input
+                .trim()
+                .chars()
+                .next()
+                .and_then(|char| Piece::from_char(char.to_ascii_uppercase()))
+        {
+            return Ok(piece);
+        }
+    }
+}
+
+fn option_value<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == name)
+        .and_then(|index| args.get(index + 1))
+        .map(String::as_str)
+}
@@ -6,7 +6,9 @@ lazy_static! {
         confy::load_path(default_parameters_location()).unwrap_or_default();
 }
 
+// Keys missing from older parameter files fall back to their defaults
 #[derive(Serialize, Deserialize)]
+#[serde(default)]
 pub struct Parameters {
     pub pawn_value: f32,
     pub bishop_value: f32,
@@ -14,6 +16,8 @@ pub struct Parameters {
     pub rook_value: f32,
     pub queen_value: f32,
     pub king_value: f32,
+    pub search_time: f32,
+    pub transposition_table_size_mb: usize,
 }
 
 impl Default for Parameters {
@@ -25,6 +29,8 @@ impl Default for Parameters {
             rook_value: 5.0,
             queen_value: 9.0,
             king_value: f32::MAX,
+            search_time: 1.0,
+            transposition_table_size_mb: 16,
         }
     }
 }
@@ -0,0 +1,20 @@
+use crate::chess_logic::{Board, GameState, Piece};
+
+pub const MATE_SCORE: f32 = 10_000.0;
+
+// Material balance from the point of view of the side to move
+pub fn evaluate(game_state: &GameState) -> f32 {
+    let board = game_state.board();
+
+    Board::squares()
+        .filter_map(|square| board[square])
+        .filter(|piece| piece.piece() != Piece::King)
+        .map(|piece| {
+            if piece.player() == game_state.turn() {
+                piece.piece().value()
+            } else {
+                -piece.piece().value()
+            }
+        })
+        .sum()
+}
@@ -0,0 +1,8 @@
+mod eval;
+mod move_ordering;
+mod negamax;
+mod transposition_table;
+
+pub use eval::{evaluate, MATE_SCORE};
+pub use negamax::{Search, SearchResult, MAX_DEPTH};
+pub use transposition_table::{Bound, TableEntry, TranspositionTable};
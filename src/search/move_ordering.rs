@@ -0,0 +1,168 @@
+use cgmath::Vector2;
+
+use crate::{
+    chess_logic::{GameState, MoveDescriptor, Piece, BOARD_SIZE},
+    search::MAX_DEPTH,
+};
+
+const SQUARE_COUNT: usize = (BOARD_SIZE as usize) * (BOARD_SIZE as usize);
+const KILLERS_PER_PLY: usize = 2;
+
+// Moves are sorted by tier first, and by the score within their tier second
+const TABLE_MOVE_TIER: u8 = 3;
+const CAPTURE_TIER: u8 = 2;
+const KILLER_TIER: u8 = 1;
+const QUIET_TIER: u8 = 0;
+
+pub(crate) struct MoveOrdering {
+    killers: Vec<[Option<MoveDescriptor>; KILLERS_PER_PLY]>,
+    history: Vec<u32>,
+}
+
+impl MoveOrdering {
+    pub(crate) fn new() -> Self {
+        Self {
+            killers: vec![[None; KILLERS_PER_PLY]; MAX_DEPTH as usize + 1],
+            history: vec![0; SQUARE_COUNT * SQUARE_COUNT],
+        }
+    }
+
+    // Killers only make sense for the position they were found in, history just fades out
+    pub(crate) fn new_search(&mut self) {
+        self.killers.fill([None; KILLERS_PER_PLY]);
+        self.history.iter_mut().for_each(|score| *score /= 2);
+    }
+
+    pub(crate) fn order(
+        &self,
+        game_state: &GameState,
+        moves: &mut [MoveDescriptor],
+        table_move: Option<MoveDescriptor>,
+        ply: u32,
+    ) {
+        sort_by_score(moves, |move_command| {
+            if Some(move_command) == table_move {
+                (TABLE_MOVE_TIER, 0.0)
+            } else if is_tactical(game_state, move_command) {
+                (CAPTURE_TIER, mvv_lva(game_state, move_command))
+            } else if let Some(index) = self.killers[ply as usize]
+                .iter()
+                .position(|&killer| killer == Some(move_command))
+            {
+                (KILLER_TIER, -(index as f32))
+            } else {
+                (QUIET_TIER, self.history[history_index(move_command)] as f32)
+            }
+        });
+    }
+
+    // Called with the position from before the move that caused the cutoff
+    pub(crate) fn add_cutoff(
+        &mut self,
+        game_state: &GameState,
+        move_command: MoveDescriptor,
+        depth: u32,
+        ply: u32,
+    ) {
+        if is_tactical(game_state, move_command) {
+            return;
+        }
+
+        let killers = &mut self.killers[ply as usize];
+        if killers[0] != Some(move_command) {
+            killers[1] = killers[0];
+            killers[0] = Some(move_command);
+        }
+
+        self.history[history_index(move_command)] += depth * depth;
+    }
+}
+
+pub(crate) fn order_captures(game_state: &GameState, moves: &mut [MoveDescriptor]) {
+    sort_by_score(moves, |move_command| {
+        (CAPTURE_TIER, mvv_lva(game_state, move_command))
+    });
+}
+
+fn sort_by_score(moves: &mut [MoveDescriptor], mut score: impl FnMut(MoveDescriptor) -> (u8, f32)) {
+    let mut scored = moves
+        .iter()
+        .map(|&move_command| (score(move_command), move_command))
+        .collect::<Vec<_>>();
+    scored.sort_by(|((a_tier, a_score), _), ((b_tier, b_score), _)| {
+        b_tier.cmp(a_tier).then(b_score.total_cmp(a_score))
+    });
+
+    for (slot, (_, move_command)) in moves.iter_mut().zip(scored) {
+        *slot = move_command;
+    }
+}
+
+// Most valuable victim first, and among those the least valuable attacker
+fn mvv_lva(game_state: &GameState, move_command: MoveDescriptor) -> f32 {
+    let board = game_state.board();
+
+    let victim = if board.is_en_passant(move_command) {
+        Piece::Pawn.value()
+    } else {
+        board[move_command.to].map_or(0.0, |piece| piece.piece().value())
+    };
+    let attacker = board[move_command.from].map_or(0.0, |piece| piece.piece().value());
+    let promotion = move_command.promotion.map_or(0.0, |piece| piece.value());
+
+    (victim + promotion) * 10.0 - attacker
+}
+
+// Captures and queen promotions, underpromotions are almost never worth the nodes
+pub(crate) fn is_tactical(game_state: &GameState, move_command: MoveDescriptor) -> bool {
+    let board = game_state.board();
+    let is_capture = board[move_command.to].is_some() || board.is_en_passant(move_command);
+
+    (is_capture || move_command.promotion.is_some())
+        && move_command
+            .promotion
+            .is_none_or(|promotion| promotion == Piece::Queen)
+}
+
+fn history_index(move_command: MoveDescriptor) -> usize {
+    let square_index = |square: Vector2<u8>| (square.y * BOARD_SIZE + square.x) as usize;
+
+    square_index(move_command.from) * SQUARE_COUNT + square_index(move_command.to)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coordinates(text: &str) -> MoveDescriptor {
+        MoveDescriptor::try_from(text.to_string()).unwrap()
+    }
+
+    #[test]
+    fn orders_moves_by_tier() {
+        let game_state = GameState::from_fen("4k3/8/8/3r1n2/4P3/8/8/4K2R w K - 0 1").unwrap();
+        let mut move_ordering = MoveOrdering::new();
+
+        move_ordering.add_cutoff(&game_state, coordinates("e1e2"), 2, 3);
+        move_ordering.add_cutoff(&game_state, coordinates("e1f2"), 2, 3);
+        // Captures are never killers, and a cutoff at another ply only counts for history
+        move_ordering.add_cutoff(&game_state, coordinates("e4f5"), 2, 3);
+        move_ordering.add_cutoff(&game_state, coordinates("h1g1"), 5, 0);
+
+        let mut moves = game_state.legal_moves();
+        move_ordering.order(&game_state, &mut moves, Some(coordinates("h1h2")), 3);
+
+        let expected = ["h1h2", "e4d5", "e4f5", "e1f2", "e1e2", "h1g1"];
+        assert_eq!(moves[..expected.len()], expected.map(coordinates));
+    }
+
+    #[test]
+    fn orders_captures_by_victim_then_attacker() {
+        let game_state = GameState::from_fen("4k3/8/8/3q4/4PN2/3r4/1N6/4K3 w - - 0 1").unwrap();
+
+        let mut moves = ["b2d3", "f4d5", "e4d5"].map(coordinates);
+        order_captures(&game_state, &mut moves);
+
+        assert_eq!(moves, ["e4d5", "f4d5", "b2d3"].map(coordinates));
+    }
+}
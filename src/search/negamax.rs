@@ -0,0 +1,340 @@
+use std::time::{Duration, Instant};
+
+use crate::{
+    chess_logic::{GameState, MoveDescriptor, FIFTY_MOVE_RULE},
+    parameters::PARAMETERS,
+    search::{
+        evaluate,
+        move_ordering::{is_tactical, order_captures, MoveOrdering},
+        transposition_table::{score_from_table, score_to_table},
+        Bound, TableEntry, TranspositionTable, MATE_SCORE,
+    },
+};
+
+pub const MAX_DEPTH: u32 = 64;
+// How many nodes go by between looking at the clock
+const TIME_CHECK_INTERVAL: u64 = 1024;
+
+#[derive(Clone, Copy, Debug)]
+pub struct SearchResult {
+    pub best_move: MoveDescriptor,
+    pub score: f32,
+    pub depth: u32,
+    pub nodes: u64,
+}
+
+pub struct Search {
+    transposition_table: TranspositionTable,
+    move_ordering: MoveOrdering,
+    deadline: Option<Instant>,
+    nodes: u64,
+    stopped: bool,
+}
+
+impl Default for Search {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Search {
+    pub fn new() -> Self {
+        Self::with_table_size(PARAMETERS.transposition_table_size_mb)
+    }
+
+    pub fn with_table_size(size_mb: usize) -> Self {
+        Self {
+            transposition_table: TranspositionTable::new(size_mb),
+            move_ordering: MoveOrdering::new(),
+            deadline: None,
+            nodes: 0,
+            stopped: false,
+        }
+    }
+
+    pub fn transposition_table(&self) -> &TranspositionTable {
+        &self.transposition_table
+    }
+
+    pub fn clear(&mut self) {
+        self.transposition_table.clear();
+        self.move_ordering = MoveOrdering::new();
+    }
+
+    // Nodes visited by the last search, quiescence nodes included
+    pub fn nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    pub fn best_move(&mut self, game_state: &GameState, depth: u32) -> Option<SearchResult> {
+        self.start_search();
+
+        self.search_root(&mut game_state.clone(), depth.clamp(1, MAX_DEPTH), None)
+    }
+
+    // Searches one ply deeper at a time and keeps the result of the last depth that finished
+    pub fn best_move_within(
+        &mut self,
+        game_state: &GameState,
+        time_budget: Duration,
+    ) -> Option<SearchResult> {
+        let deadline = Instant::now() + time_budget;
+        let mut game_state = game_state.clone();
+        let mut result: Option<SearchResult> = None;
+
+        // The first depth always runs to completion so there is a move to return
+        self.start_search();
+
+        for depth in 1..=MAX_DEPTH {
+            let previous_best = result.map(|result| result.best_move);
+
+            match self.search_root(&mut game_state, depth, previous_best) {
+                Some(depth_result) if !self.stopped => result = Some(depth_result),
+                _ => break,
+            }
+
+            if result.is_some_and(|result| result.score.abs() >= MATE_SCORE - MAX_DEPTH as f32) {
+                break;
+            }
+
+            self.deadline = Some(deadline);
+        }
+
+        result
+    }
+
+    fn start_search(&mut self) {
+        self.deadline = None;
+        self.stopped = false;
+        self.nodes = 0;
+        self.move_ordering.new_search();
+    }
+
+    fn search_root(
+        &mut self,
+        game_state: &mut GameState,
+        depth: u32,
+        previous_best: Option<MoveDescriptor>,
+    ) -> Option<SearchResult> {
+        let mut moves = game_state.legal_moves();
+        // The best move of the previous depth is the most likely to be best again
+        let table_move = previous_best.or_else(|| {
+            self.transposition_table
+                .probe(game_state.zobrist_key())
+                .and_then(|entry| entry.best_move)
+        });
+        self.move_ordering
+            .order(game_state, &mut moves, table_move, 0);
+
+        let mut alpha = -f32::INFINITY;
+        let mut best_move = None;
+
+        for move_command in moves {
+            game_state.apply_move(move_command);
+            let score = -self.negamax(game_state, depth - 1, 1, -f32::INFINITY, -alpha);
+            game_state.revert_move();
+
+            if self.stopped {
+                return None;
+            }
+
+            if best_move.is_none() || score > alpha {
+                alpha = score;
+                best_move = Some(move_command);
+            }
+        }
+
+        let best_move = best_move?;
+        self.transposition_table.store(TableEntry {
+            key: game_state.zobrist_key(),
+            depth,
+            score: alpha,
+            bound: Bound::Exact,
+            best_move: Some(best_move),
+        });
+
+        Some(SearchResult {
+            best_move,
+            score: alpha,
+            depth,
+            nodes: self.nodes,
+        })
+    }
+
+    fn negamax(
+        &mut self,
+        game_state: &mut GameState,
+        depth: u32,
+        ply: u32,
+        mut alpha: f32,
+        beta: f32,
+    ) -> f32 {
+        if self.should_stop() || is_draw(game_state) {
+            return 0.0;
+        }
+
+        if depth == 0 {
+            return self.quiescence(game_state, ply, alpha, beta);
+        }
+
+        let key = game_state.zobrist_key();
+        let table_entry = self.transposition_table.probe(key);
+        if let Some(entry) = table_entry.filter(|entry| entry.depth >= depth) {
+            let score = score_from_table(entry.score, ply);
+
+            match entry.bound {
+                Bound::Exact => return score,
+                Bound::Lower if score >= beta => return score,
+                Bound::Upper if score <= alpha => return score,
+                _ => {}
+            }
+        }
+
+        let mut moves = game_state.legal_moves();
+        if moves.is_empty() {
+            // Quicker mates score higher
+            return if game_state.in_check() {
+                -MATE_SCORE + ply as f32
+            } else {
+                0.0
+            };
+        }
+
+        self.move_ordering.order(
+            game_state,
+            &mut moves,
+            table_entry.and_then(|entry| entry.best_move),
+            ply,
+        );
+
+        let original_alpha = alpha;
+        let mut best_score = -f32::INFINITY;
+        let mut best_move = None;
+
+        for move_command in moves {
+            game_state.apply_move(move_command);
+            let score = -self.negamax(game_state, depth - 1, ply + 1, -beta, -alpha);
+            game_state.revert_move();
+
+            if self.stopped {
+                return 0.0;
+            }
+            if score > best_score {
+                best_score = score;
+                best_move = Some(move_command);
+            }
+            if score > alpha {
+                alpha = score;
+            }
+            if alpha >= beta {
+                self.move_ordering
+                    .add_cutoff(game_state, move_command, depth, ply);
+                break;
+            }
+        }
+
+        let bound = if best_score <= original_alpha {
+            Bound::Upper
+        } else if best_score >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        self.transposition_table.store(TableEntry {
+            key,
+            depth,
+            score: score_to_table(best_score, ply),
+            bound,
+            best_move,
+        });
+
+        best_score
+    }
+
+    // Plays out captures and promotions until the position is quiet enough to trust the eval
+    fn quiescence(
+        &mut self,
+        game_state: &mut GameState,
+        ply: u32,
+        mut alpha: f32,
+        beta: f32,
+    ) -> f32 {
+        if self.should_stop() {
+            return 0.0;
+        }
+
+        let mut moves = game_state.legal_moves();
+        let in_check = game_state.in_check();
+        if moves.is_empty() {
+            return if in_check {
+                -MATE_SCORE + ply as f32
+            } else {
+                0.0
+            };
+        }
+
+        if ply >= MAX_DEPTH || game_state.board().is_dead_position() {
+            return evaluate(game_state);
+        }
+
+        // Standing pat is not an option in check, so every evasion gets searched
+        let mut best_score = -f32::INFINITY;
+        if !in_check {
+            best_score = evaluate(game_state);
+            if best_score >= beta {
+                return best_score;
+            }
+            if best_score > alpha {
+                alpha = best_score;
+            }
+        }
+
+        if !in_check {
+            moves.retain(|&move_command| is_tactical(game_state, move_command));
+        }
+        order_captures(game_state, &mut moves);
+
+        for move_command in moves {
+            game_state.apply_move(move_command);
+            let score = -self.quiescence(game_state, ply + 1, -beta, -alpha);
+            game_state.revert_move();
+
+            if self.stopped {
+                return 0.0;
+            }
+            if score > best_score {
+                best_score = score;
+            }
+            if score > alpha {
+                alpha = score;
+            }
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        best_score
+    }
+
+    fn should_stop(&mut self) -> bool {
+        self.nodes += 1;
+        if self.nodes.is_multiple_of(TIME_CHECK_INTERVAL)
+            && self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            self.stopped = true;
+        }
+
+        self.stopped
+    }
+}
+
+// Any repetition inside the search is treated as a draw, since the side that can avoid it will.
+// A mate on the move that reaches the fifty-move limit still counts, so that case is left to
+// the mate check.
+fn is_draw(game_state: &GameState) -> bool {
+    game_state.repetitions() > 1
+        || game_state.board().is_dead_position()
+        || (game_state.halfmove_clock() >= FIFTY_MOVE_RULE && !game_state.legal_moves().is_empty())
+}
@@ -0,0 +1,147 @@
+use std::mem;
+
+use crate::{
+    chess_logic::MoveDescriptor,
+    search::{MATE_SCORE, MAX_DEPTH},
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Bound {
+    Exact,
+    Lower,
+    Upper,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct TableEntry {
+    pub key: u64,
+    pub depth: u32,
+    pub score: f32,
+    pub bound: Bound,
+    pub best_move: Option<MoveDescriptor>,
+}
+
+pub struct TranspositionTable {
+    entries: Vec<Option<TableEntry>>,
+}
+
+impl TranspositionTable {
+    pub fn new(size_mb: usize) -> Self {
+        let len = (size_mb * 1024 * 1024 / mem::size_of::<Option<TableEntry>>()).max(1);
+
+        Self {
+            entries: vec![None; len],
+        }
+    }
+
+    pub fn probe(&self, key: u64) -> Option<TableEntry> {
+        self.entries[self.index(key)].filter(|entry| entry.key == key)
+    }
+
+    // Keeps the deeper of two entries for the same position, otherwise the newer one
+    pub fn store(&mut self, entry: TableEntry) {
+        let index = self.index(entry.key);
+
+        match self.entries[index] {
+            Some(old) if old.key == entry.key && old.depth > entry.depth => {}
+            _ => self.entries[index] = Some(entry),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.fill(None);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn index(&self, key: u64) -> usize {
+        (key % self.entries.len() as u64) as usize
+    }
+}
+
+// Mate scores count plies from the root, but the table needs them relative to the stored node
+pub(crate) fn score_to_table(score: f32, ply: u32) -> f32 {
+    if score >= MATE_SCORE - MAX_DEPTH as f32 {
+        score + ply as f32
+    } else if score <= -MATE_SCORE + MAX_DEPTH as f32 {
+        score - ply as f32
+    } else {
+        score
+    }
+}
+
+pub(crate) fn score_from_table(score: f32, ply: u32) -> f32 {
+    if score >= MATE_SCORE - MAX_DEPTH as f32 {
+        score - ply as f32
+    } else if score <= -MATE_SCORE + MAX_DEPTH as f32 {
+        score + ply as f32
+    } else {
+        score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: u64, depth: u32) -> TableEntry {
+        TableEntry {
+            key,
+            depth,
+            score: depth as f32,
+            bound: Bound::Exact,
+            best_move: None,
+        }
+    }
+
+    #[test]
+    fn keeps_the_deeper_entry_for_a_position() {
+        let mut table = TranspositionTable::new(1);
+
+        table.store(entry(7, 4));
+        table.store(entry(7, 2));
+        assert_eq!(table.probe(7).map(|entry| entry.depth), Some(4));
+
+        table.store(entry(7, 5));
+        assert_eq!(table.probe(7).map(|entry| entry.depth), Some(5));
+    }
+
+    #[test]
+    fn rejects_entries_for_other_positions() {
+        // Less than one entry's worth of memory still leaves a single slot, so every key collides
+        let mut table = TranspositionTable::new(0);
+        assert_eq!(table.len(), 1);
+
+        table.store(entry(1, 6));
+        assert!(table.probe(2).is_none());
+
+        // A shallower entry for another position still replaces the old one
+        table.store(entry(2, 1));
+        assert!(table.probe(1).is_none());
+        assert_eq!(table.probe(2).map(|entry| entry.depth), Some(1));
+
+        table.clear();
+        assert!(table.probe(2).is_none());
+    }
+
+    #[test]
+    fn stores_mate_scores_relative_to_the_node() {
+        // Mate in three plies from the root, found at ply 2, is mate in one from that node
+        let mate = MATE_SCORE - 3.0;
+        assert_eq!(score_to_table(mate, 2), MATE_SCORE - 1.0);
+        assert_eq!(score_from_table(MATE_SCORE - 1.0, 2), mate);
+        assert_eq!(score_from_table(MATE_SCORE - 1.0, 5), MATE_SCORE - 6.0);
+
+        assert_eq!(score_to_table(-mate, 2), -MATE_SCORE + 1.0);
+        assert_eq!(score_from_table(-MATE_SCORE + 1.0, 2), -mate);
+
+        assert_eq!(score_to_table(150.0, 2), 150.0);
+        assert_eq!(score_from_table(-150.0, 2), -150.0);
+    }
+}
@@ -0,0 +1,58 @@
+use cgmath::Vector2;
+use chess_bot::chess_logic::{parse_square, Board, GameState, Player, STARTING_FEN};
+
+fn square(name: &str) -> Vector2<u8> {
+    parse_square(name).unwrap()
+}
+
+fn sorted(mut squares: Vec<Vector2<u8>>) -> Vec<Vector2<u8>> {
+    squares.sort_by_key(|square| (square.y, square.x));
+    squares
+}
+
+#[test]
+fn lists_every_attacker() {
+    let attack_map = Board::new().attack_map(Player::White);
+
+    assert_eq!(attack_map.player(), Player::White);
+    assert_eq!(
+        sorted(attack_map.attackers(square("f3")).to_vec()),
+        sorted(vec![square("e2"), square("g2"), square("g1")])
+    );
+    assert!(attack_map.attackers(square("e5")).is_empty());
+    assert!(!attack_map.is_attacked(square("e5")));
+}
+
+#[test]
+fn pawn_pushes_are_not_attacks() {
+    let game_state = GameState::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1").unwrap();
+    let attack_map = game_state.board().attack_map(Player::White);
+
+    assert!(!attack_map.is_attacked(square("e3")));
+    assert!(!attack_map.is_attacked(square("e4")));
+    assert!(attack_map.is_attacked(square("d3")));
+    assert!(attack_map.is_attacked(square("f3")));
+}
+
+#[test]
+fn friendly_pieces_count_as_defended() {
+    let attack_map = GameState::from_fen(STARTING_FEN)
+        .unwrap()
+        .board()
+        .attack_map(Player::Black);
+
+    assert_eq!(attack_map.attackers(square("d7")).len(), 4);
+    assert!(attack_map.is_attacked(square("b8")));
+    assert!(!attack_map.is_attacked(square("a8")));
+}
+
+#[test]
+fn sliding_pieces_stop_at_the_first_blocker() {
+    let game_state = GameState::from_fen("4k3/8/8/8/p7/8/8/R3K3 w - - 0 1").unwrap();
+    let attack_map = game_state.board().attack_map(Player::White);
+
+    assert_eq!(attack_map.attackers(square("a4")), [square("a1")]);
+    assert!(!attack_map.is_attacked(square("a5")));
+    assert_eq!(attack_map.attackers(square("e1")), [square("a1")]);
+    assert_eq!(attack_map.attackers(square("f1")), [square("e1")]);
+}
@@ -0,0 +1,54 @@
+use chess_bot::chess_logic::{BoardBuilder, BoardBuilderError, OwnedPiece, Piece, Player};
+
+fn kings() -> BoardBuilder {
+    BoardBuilder::new()
+        .place((4, 0), OwnedPiece::new(Player::White, Piece::King))
+        .place((4, 7), OwnedPiece::new(Player::Black, Piece::King))
+}
+
+#[test]
+fn builds_valid_position() {
+    let game_state = kings()
+        .place((0, 1), OwnedPiece::new(Player::White, Piece::Pawn))
+        .build()
+        .unwrap();
+
+    assert_eq!(game_state.to_fen(), "4k3/8/8/8/8/8/P7/4K3 w - - 0 1");
+}
+
+#[test]
+fn rejects_off_board_squares() {
+    let rook = OwnedPiece::new(Player::White, Piece::Rook);
+
+    assert_eq!(
+        kings().place((8, 0), rook).build().unwrap_err(),
+        BoardBuilderError::OffBoard((8, 0).into())
+    );
+    assert_eq!(
+        kings().place((0, 8), rook).build().unwrap_err(),
+        BoardBuilderError::OffBoard((0, 8).into())
+    );
+    assert_eq!(
+        kings().remove((9, 9)).build().unwrap_err(),
+        BoardBuilderError::OffBoard((9, 9).into())
+    );
+}
+
+#[test]
+fn rejects_missing_king() {
+    assert_eq!(
+        kings().remove((4, 7)).build().unwrap_err(),
+        BoardBuilderError::WrongKingCount(Player::Black)
+    );
+}
+
+#[test]
+fn rejects_pawn_on_back_rank() {
+    assert_eq!(
+        kings()
+            .place((0, 7), OwnedPiece::new(Player::White, Piece::Pawn))
+            .build()
+            .unwrap_err(),
+        BoardBuilderError::PawnOnBackRank((0, 7).into())
+    );
+}
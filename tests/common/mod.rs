@@ -0,0 +1,5 @@
+use chess_bot::chess_logic::MoveDescriptor;
+
+pub fn coordinates(text: &str) -> MoveDescriptor {
+    MoveDescriptor::try_from(text.to_string()).unwrap()
+}
@@ -0,0 +1,58 @@
+use chess_bot::chess_logic::{GameState, StateUpdate};
+
+mod common;
+
+use common::coordinates;
+
+// Every position has a rook on e2 for white to capture, leaving the material under test
+fn capture_rook(fen: &str, capture: &str) -> StateUpdate {
+    GameState::from_fen(fen).unwrap().play(coordinates(capture))
+}
+
+#[test]
+fn king_against_king() {
+    assert!(matches!(
+        capture_rook("4k3/8/8/8/8/8/4r3/4K3 w - - 0 1", "e1e2"),
+        StateUpdate::Draw
+    ));
+}
+
+#[test]
+fn king_and_bishop_against_king() {
+    assert!(matches!(
+        capture_rook("4k3/8/8/8/8/8/4r3/3BK3 w - - 0 1", "d1e2"),
+        StateUpdate::Draw
+    ));
+}
+
+#[test]
+fn king_and_knight_against_king() {
+    assert!(matches!(
+        capture_rook("4k3/8/8/8/8/8/4r3/4K1N1 w - - 0 1", "g1e2"),
+        StateUpdate::Draw
+    ));
+}
+
+#[test]
+fn bishops_on_the_same_colour() {
+    assert!(matches!(
+        capture_rook("2b1k3/8/8/8/8/8/4r3/3BK3 w - - 0 1", "d1e2"),
+        StateUpdate::Draw
+    ));
+}
+
+#[test]
+fn bishops_on_opposite_colours_play_on() {
+    assert!(matches!(
+        capture_rook("3bk3/8/8/8/8/8/4r3/3BK3 w - - 0 1", "d1e2"),
+        StateUpdate::Continue
+    ));
+}
+
+#[test]
+fn bishop_and_knight_play_on() {
+    assert!(matches!(
+        capture_rook("4k3/8/8/8/8/8/4r3/3BK1N1 w - - 0 1", "d1e2"),
+        StateUpdate::Continue
+    ));
+}
@@ -0,0 +1,137 @@
+use chess_bot::chess_logic::{GameState, Player, StateUpdate};
+
+mod common;
+
+use common::coordinates;
+
+const KNIGHT_SHUFFLE: [&str; 4] = ["g1f3", "g8f6", "f3g1", "f6g8"];
+
+// Plays the knight shuffle the given number of times and returns the last update
+fn shuffle_knights(game_state: &mut GameState, times: usize) -> StateUpdate {
+    let mut state_update = StateUpdate::Continue;
+
+    for _ in 0..times {
+        for text in KNIGHT_SHUFFLE {
+            assert!(matches!(state_update, StateUpdate::Continue));
+            state_update = game_state.play(coordinates(text));
+        }
+    }
+
+    state_update
+}
+
+#[test]
+fn threefold_repetition_draws_by_default() {
+    let mut game_state = GameState::new(Player::White);
+
+    assert!(matches!(
+        shuffle_knights(&mut game_state, 2),
+        StateUpdate::Draw
+    ));
+    assert_eq!(game_state.repetitions(), 3);
+    assert!(matches!(game_state.outcome(), Some(StateUpdate::Draw)));
+}
+
+#[test]
+fn threefold_repetition_can_be_claimed() {
+    let mut game_state = GameState::new(Player::White);
+    game_state.set_claim_based_draws(true);
+
+    assert!(matches!(
+        shuffle_knights(&mut game_state, 2),
+        StateUpdate::Continue
+    ));
+    assert!(game_state.can_claim_draw());
+    assert!(game_state.outcome().is_none());
+    assert!(matches!(game_state.claim_draw(), StateUpdate::Draw));
+    assert!(matches!(game_state.outcome(), Some(StateUpdate::Draw)));
+}
+
+#[test]
+fn claim_is_refused_without_grounds() {
+    let mut game_state = GameState::new(Player::White);
+    game_state.set_claim_based_draws(true);
+    shuffle_knights(&mut game_state, 1);
+
+    assert!(!game_state.can_claim_draw());
+    assert!(matches!(game_state.claim_draw(), StateUpdate::Continue));
+    assert!(game_state.outcome().is_none());
+}
+
+#[test]
+fn fivefold_repetition_draws_in_claim_mode() {
+    let mut game_state = GameState::new(Player::White);
+    game_state.set_claim_based_draws(true);
+
+    assert!(matches!(
+        shuffle_knights(&mut game_state, 3),
+        StateUpdate::Continue
+    ));
+    assert!(matches!(
+        shuffle_knights(&mut game_state, 1),
+        StateUpdate::Draw
+    ));
+    assert_eq!(game_state.repetitions(), 5);
+}
+
+#[test]
+fn fifty_move_rule_draws_by_default() {
+    let mut game_state = GameState::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 99 80").unwrap();
+
+    assert!(matches!(
+        game_state.play(coordinates("a1a2")),
+        StateUpdate::Draw
+    ));
+    assert_eq!(game_state.halfmove_clock(), 100);
+}
+
+#[test]
+fn fifty_move_rule_can_be_claimed() {
+    let mut game_state = GameState::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 99 80").unwrap();
+    game_state.set_claim_based_draws(true);
+
+    assert!(matches!(
+        game_state.play(coordinates("a1a2")),
+        StateUpdate::Continue
+    ));
+    assert!(game_state.can_claim_draw());
+}
+
+#[test]
+fn seventy_five_move_rule_draws_in_claim_mode() {
+    let mut game_state = GameState::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 149 80").unwrap();
+    game_state.set_claim_based_draws(true);
+
+    assert!(matches!(
+        game_state.play(coordinates("a1a2")),
+        StateUpdate::Draw
+    ));
+}
+
+#[test]
+fn pawn_moves_and_captures_reset_the_clock() {
+    let mut game_state = GameState::from_fen("4k3/8/8/8/1p6/8/P7/R3K3 w - - 99 80").unwrap();
+
+    assert!(matches!(
+        game_state.play(coordinates("a2a3")),
+        StateUpdate::Continue
+    ));
+    assert_eq!(game_state.halfmove_clock(), 0);
+
+    game_state.play(coordinates("e8e7"));
+    assert!(matches!(
+        game_state.play(coordinates("a3b4")),
+        StateUpdate::Continue
+    ));
+    assert_eq!(game_state.halfmove_clock(), 0);
+}
+
+#[test]
+fn mate_on_the_hundredth_halfmove_wins() {
+    let mut game_state = GameState::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 99 80").unwrap();
+
+    assert!(matches!(
+        game_state.play(coordinates("a1a8")),
+        StateUpdate::Win(Player::White)
+    ));
+}
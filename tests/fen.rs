@@ -0,0 +1,120 @@
+use chess_bot::chess_logic::{BoardBuilderError, FenError, GameState, Player, STARTING_FEN};
+
+fn error(fen: &str) -> FenError {
+    GameState::from_fen(fen).unwrap_err()
+}
+
+#[test]
+fn round_trips_the_starting_position() {
+    assert_eq!(
+        GameState::from_fen(STARTING_FEN).unwrap().to_fen(),
+        STARTING_FEN
+    );
+    assert_eq!(GameState::new(Player::White).to_fen(), STARTING_FEN);
+}
+
+#[test]
+fn round_trips_en_passant_and_partial_castling() {
+    let fen = "r3k2r/pp1ppppp/8/2pP4/8/8/PPP1PPPP/R3K2R w Kq c6 0 5";
+    let game_state = GameState::from_fen(fen).unwrap();
+
+    assert_eq!(game_state.to_fen(), fen);
+    assert_eq!(game_state.en_passant(), Some((2, 5).into()));
+    assert!(game_state.castling_rights().white_king_side);
+    assert!(!game_state.castling_rights().white_queen_side);
+    assert!(!game_state.castling_rights().black_king_side);
+    assert!(game_state.castling_rights().black_queen_side);
+}
+
+#[test]
+fn fills_in_missing_counters() {
+    let game_state = GameState::from_fen("4k3/8/8/8/8/8/8/4K3 b - -").unwrap();
+
+    assert_eq!(game_state.to_fen(), "4k3/8/8/8/8/8/8/4K3 b - - 0 1");
+}
+
+#[test]
+fn names_the_field_that_failed() {
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4K3 w - - 0"),
+        FenError::WrongFieldCount(5)
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4K4 w - - 0 1"),
+        FenError::PiecePlacement
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/4K3 w - - 0 1"),
+        FenError::PiecePlacement
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4X3 w - - 0 1"),
+        FenError::PiecePlacement
+    );
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K3 x - - 0 1"), FenError::SideToMove);
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K3 w - e9 0 1"), FenError::EnPassant);
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4K3 w - - x 1"),
+        FenError::HalfmoveClock
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4K3 w - - 0 x"),
+        FenError::FullmoveNumber
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4K3 w - - 0 0"),
+        FenError::FullmoveNumber
+    );
+}
+
+#[test]
+fn rejects_bad_castling_rights() {
+    let start = "r3k2r/8/8/8/8/8/8/R3K2R w";
+
+    assert_eq!(
+        error(&format!("{start} KK - 0 1")),
+        FenError::CastlingRights
+    );
+    assert_eq!(
+        error(&format!("{start} KX - 0 1")),
+        FenError::CastlingRights
+    );
+    assert_eq!(
+        error(&format!("{start} KQkq- - 0 1")),
+        FenError::CastlingRights
+    );
+    // The right is fine on its own, but there is no rook on h1 to castle with
+    assert_eq!(
+        error("r3k2r/8/8/8/8/8/8/R3K3 w K - 0 1"),
+        FenError::CastlingRights
+    );
+}
+
+#[test]
+fn rejects_en_passant_on_the_wrong_rank() {
+    assert_eq!(
+        error("4k3/8/8/2pP4/8/8/8/4K3 w - c5 0 1"),
+        FenError::EnPassant
+    );
+    assert_eq!(
+        error("4k3/8/8/2pP4/8/8/8/4K3 w - c3 0 1"),
+        FenError::EnPassant
+    );
+    // Right rank, but no pawn just moved there
+    assert_eq!(
+        error("4k3/8/8/3P4/8/8/8/4K3 w - c6 0 1"),
+        FenError::EnPassant
+    );
+}
+
+#[test]
+fn rejects_impossible_positions() {
+    assert_eq!(
+        error("8/8/8/8/8/8/8/4K3 w - - 0 1"),
+        FenError::InvalidPosition(BoardBuilderError::WrongKingCount(Player::Black))
+    );
+    assert_eq!(
+        error("4k3/4R3/8/8/8/8/8/4K3 w - - 0 1"),
+        FenError::InvalidPosition(BoardBuilderError::OpponentInCheck)
+    );
+}
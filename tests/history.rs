@@ -0,0 +1,64 @@
+use chess_bot::chess_logic::{GameState, Piece, StateUpdate};
+
+mod common;
+
+use common::coordinates;
+
+fn assert_position(game_state: &GameState, fen: &str) {
+    assert_eq!(game_state.to_fen(), fen);
+    assert_eq!(
+        game_state.zobrist_key(),
+        GameState::from_fen(fen).unwrap().zobrist_key()
+    );
+}
+
+#[test]
+fn undoes_and_redoes_special_moves() {
+    let start = "r3k3/P7/8/8/3p4/8/4P3/R3K2R w KQq - 0 1";
+    // Double push, en passant, castling on both sides and a promotion
+    let moves = ["e2e4", "d4e3", "e1g1", "e8c8", "a7a8q"];
+
+    let mut game_state = GameState::from_fen(start).unwrap();
+    let mut fens = vec![game_state.to_fen()];
+    for text in moves {
+        assert!(!matches!(
+            game_state.play(coordinates(text)),
+            StateUpdate::InvalidMove(_)
+        ));
+        fens.push(game_state.to_fen());
+    }
+    assert_eq!(fens[5], "Q1kr4/8/8/8/8/4p3/8/R4RK1 b - - 0 3");
+
+    let entry = game_state.history()[1];
+    assert_eq!(entry.captured.map(|piece| piece.piece()), Some(Piece::Pawn));
+    assert_eq!(entry.en_passant, Some((4, 2).into()));
+
+    for index in (0..moves.len()).rev() {
+        assert_eq!(game_state.undo(), Some(coordinates(moves[index])));
+        assert_position(&game_state, &fens[index]);
+        assert_eq!(game_state.moves().count(), index);
+    }
+    assert_eq!(game_state.undo(), None);
+
+    for (index, fen) in fens.iter().enumerate().skip(1) {
+        assert!(game_state.redo().is_some());
+        assert_position(&game_state, fen);
+        assert_eq!(
+            game_state.moves().last(),
+            Some(coordinates(moves[index - 1]))
+        );
+    }
+    assert!(game_state.redo().is_none());
+}
+
+#[test]
+fn playing_a_new_move_clears_redo() {
+    let mut game_state = GameState::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1").unwrap();
+
+    game_state.play(coordinates("e2e4"));
+    game_state.undo();
+    game_state.play(coordinates("e2e3"));
+
+    assert!(game_state.redo().is_none());
+    assert_position(&game_state, "4k3/8/8/8/8/4P3/8/4K3 b - - 0 1");
+}
@@ -0,0 +1,51 @@
+use chess_bot::chess_logic::{parse_square, MoveDescriptor, MoveParseError, Piece};
+
+fn parse(text: &str) -> Result<MoveDescriptor, MoveParseError> {
+    MoveDescriptor::try_from(text.to_string())
+}
+
+#[test]
+fn parses_coordinates() {
+    let move_command = parse("e2e4").unwrap();
+
+    assert_eq!(move_command.from, (4, 1).into());
+    assert_eq!(move_command.to, (4, 3).into());
+    assert_eq!(move_command.promotion, None);
+}
+
+#[test]
+fn parses_promotions() {
+    for (text, piece) in [
+        ("e7e8q", Piece::Queen),
+        ("e7e8r", Piece::Rook),
+        ("e7e8B", Piece::Bishop),
+        ("e7e8n", Piece::Knight),
+    ] {
+        assert_eq!(parse(text).unwrap().promotion, Some(piece));
+    }
+}
+
+#[test]
+fn rejects_kings_and_pawns_as_promotions() {
+    assert_eq!(parse("e7e8k"), Err(MoveParseError::BadPromotion('k')));
+    assert_eq!(parse("e7e8p"), Err(MoveParseError::BadPromotion('p')));
+    assert_eq!(parse("e7e8x"), Err(MoveParseError::BadPromotion('x')));
+}
+
+#[test]
+fn reports_bad_files_and_ranks() {
+    assert_eq!(parse("x2e4"), Err(MoveParseError::BadFile('x')));
+    assert_eq!(parse("e2i4"), Err(MoveParseError::BadFile('i')));
+    assert_eq!(parse("E2e4"), Err(MoveParseError::BadFile('E')));
+    assert_eq!(parse("e2ex"), Err(MoveParseError::BadRank('x')));
+    assert_eq!(parse("e2e9"), Err(MoveParseError::OffBoard));
+    assert_eq!(parse("e0e4"), Err(MoveParseError::OffBoard));
+    assert_eq!(parse("e2e"), Err(MoveParseError::WrongLength(3)));
+}
+
+#[test]
+fn parses_squares() {
+    assert_eq!(parse_square("h8"), Ok((7, 7).into()));
+    assert_eq!(parse_square("z1"), Err(MoveParseError::BadFile('z')));
+    assert_eq!(parse_square("a9"), Err(MoveParseError::OffBoard));
+}
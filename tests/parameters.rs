@@ -0,0 +1,22 @@
+use std::{env, fs};
+
+use chess_bot::parameters::Parameters;
+
+#[test]
+fn fills_in_missing_keys() {
+    let path = env::temp_dir().join(format!("chess_bot_parameters_{}.toml", std::process::id()));
+    fs::write(&path, "pawn_value = 2.0\nqueen_value = 8.5\n").unwrap();
+
+    let parameters = confy::load_path::<Parameters>(&path);
+    fs::remove_file(&path).unwrap();
+    let parameters = parameters.unwrap();
+
+    assert_eq!(parameters.pawn_value, 2.0);
+    assert_eq!(parameters.queen_value, 8.5);
+    assert_eq!(parameters.rook_value, Parameters::default().rook_value);
+    assert_eq!(parameters.search_time, Parameters::default().search_time);
+    assert_eq!(
+        parameters.transposition_table_size_mb,
+        Parameters::default().transposition_table_size_mb
+    );
+}
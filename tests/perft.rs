@@ -0,0 +1,80 @@
+use chess_bot::chess_logic::{GameState, STARTING_FEN};
+
+fn perft(fen: &str, depth: u32) -> u64 {
+    GameState::from_fen(fen).unwrap().perft(depth)
+}
+
+#[test]
+fn starting_position() {
+    assert_eq!(perft(STARTING_FEN, 1), 20);
+    assert_eq!(perft(STARTING_FEN, 2), 400);
+    assert_eq!(perft(STARTING_FEN, 3), 8_902);
+    assert_eq!(perft(STARTING_FEN, 4), 197_281);
+}
+
+#[test]
+fn kiwipete() {
+    let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+    assert_eq!(perft(fen, 1), 48);
+    assert_eq!(perft(fen, 2), 2_039);
+    assert_eq!(perft(fen, 3), 97_862);
+}
+
+#[test]
+fn position_3() {
+    let fen = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1";
+
+    assert_eq!(perft(fen, 1), 14);
+    assert_eq!(perft(fen, 2), 191);
+    assert_eq!(perft(fen, 3), 2_812);
+    assert_eq!(perft(fen, 4), 43_238);
+}
+
+#[test]
+fn position_4() {
+    let fen = "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1";
+
+    assert_eq!(perft(fen, 1), 6);
+    assert_eq!(perft(fen, 2), 264);
+    assert_eq!(perft(fen, 3), 9_467);
+}
+
+#[test]
+fn position_4_mirrored() {
+    let fen = "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1";
+
+    assert_eq!(perft(fen, 1), 6);
+    assert_eq!(perft(fen, 2), 264);
+    assert_eq!(perft(fen, 3), 9_467);
+}
+
+#[test]
+fn position_5() {
+    let fen = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
+
+    assert_eq!(perft(fen, 1), 44);
+    assert_eq!(perft(fen, 2), 1_486);
+    assert_eq!(perft(fen, 3), 62_379);
+}
+
+#[test]
+fn position_6() {
+    let fen = "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10";
+
+    assert_eq!(perft(fen, 1), 46);
+    assert_eq!(perft(fen, 2), 2_079);
+    assert_eq!(perft(fen, 3), 89_890);
+}
+
+#[test]
+fn divide_adds_up_to_perft() {
+    let game_state = GameState::from_fen(STARTING_FEN).unwrap();
+    let divide = game_state.divide(3);
+
+    assert_eq!(divide.len(), 20);
+    assert_eq!(
+        divide.iter().map(|(_, nodes)| nodes).sum::<u64>(),
+        game_state.perft(3)
+    );
+}
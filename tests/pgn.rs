@@ -0,0 +1,115 @@
+use chess_bot::chess_logic::{
+    read_pgn, GameState, MoveParseError, PgnError, PgnTags, Player, StateUpdate,
+};
+
+mod common;
+
+use common::coordinates;
+
+const TWO_GAMES: &str = r#"[Event "First"]
+[White "Alice \"A\""]
+[Result "1-0"]
+
+1. e4 {a comment (not a variation)} e5 $1 2. Nf3 (2. f4 exf4 {nested} (2... d5))
+2... Nc6 ; the rest of this line is ignored 3. Bxh7
+% escaped line 3. Bxh7
+3. Bb5 a6 4.Ba4 Nf6 5. O-O 1-0
+
+[Event "Second"]
+[SetUp "1"]
+[FEN "4k3/8/8/8/8/8/8/4K2R w K - 0 1"]
+
+1. O-O Kd7 *
+"#;
+
+#[test]
+fn reads_multiple_games() {
+    let games = read_pgn(TWO_GAMES).unwrap();
+    assert_eq!(games.len(), 2);
+
+    let first = &games[0];
+    assert_eq!(first.result, "1-0");
+    assert_eq!(first.moves.len(), 9);
+    assert_eq!(first.moves[8], coordinates("e1g1"));
+    assert_eq!(first.tags.event, "First");
+    assert_eq!(first.tags.white, "Alice \"A\"");
+    assert!(matches!(
+        first.game_state.outcome(),
+        Some(StateUpdate::Win(Player::White))
+    ));
+
+    let second = &games[1];
+    assert_eq!(second.result, "*");
+    assert_eq!(second.moves, [coordinates("e1g1"), coordinates("e8d7")]);
+    assert_eq!(second.game_state.to_fen(), "8/3k4/8/8/8/8/8/5RK1 w - - 2 2");
+    assert!(second.game_state.outcome().is_none());
+}
+
+#[test]
+fn ends_game_without_result_at_next_tags() {
+    let games = read_pgn("[Event \"A\"]\n\n1. e4 e5\n\n[Event \"B\"]\n\n1. d4 *").unwrap();
+
+    assert_eq!(games.len(), 2);
+    assert_eq!(games[0].result, "*");
+    assert_eq!(games[0].moves.len(), 2);
+    assert_eq!(games[1].moves, [coordinates("d2d4")]);
+}
+
+#[test]
+fn reports_first_illegal_move() {
+    let pgn = "1. e4 e5 2. Nf3 Nc6 *\n\n1. e4 e5 2. Nf3 Nc6 3. Ke3 Nf6 *";
+
+    assert_eq!(
+        read_pgn(pgn).unwrap_err(),
+        PgnError::IllegalMove {
+            game: 2,
+            ply: 5,
+            san: "Ke3".to_string(),
+            error: Some(MoveParseError::NoMatchingMove),
+        }
+    );
+}
+
+#[test]
+fn reports_malformed_input() {
+    assert_eq!(
+        read_pgn("1. e4 {never closed").unwrap_err(),
+        PgnError::UnterminatedComment
+    );
+    assert_eq!(
+        read_pgn("1. e4 (1. d4 d5").unwrap_err(),
+        PgnError::UnterminatedVariation
+    );
+    assert_eq!(
+        read_pgn("[Event First]\n1. e4 *").unwrap_err(),
+        PgnError::InvalidTag
+    );
+    assert!(matches!(
+        read_pgn("[FEN \"not a fen\"]\n*").unwrap_err(),
+        PgnError::InvalidFen { game: 1, .. }
+    ));
+}
+
+#[test]
+fn round_trips_exported_games() {
+    let mut game_state = GameState::from_fen("4k3/P7/8/8/8/8/8/4K3 b - - 0 1").unwrap();
+    for san in ["Kd7", "a8=Q", "Kc7", "Qe4"] {
+        let move_command = game_state.parse_san(san).unwrap();
+        game_state.play(move_command);
+    }
+    game_state.claim_draw();
+
+    let tags = PgnTags {
+        event: "Round trip".to_string(),
+        white_engine: Some("chess_bot".to_string()),
+        ..PgnTags::default()
+    };
+    let exported = game_state.to_pgn(&tags);
+    let games = read_pgn(&exported).unwrap();
+    assert_eq!(games.len(), 1);
+
+    let imported = &games[0];
+    assert!(imported.moves.iter().copied().eq(game_state.moves()));
+    assert_eq!(imported.game_state.to_fen(), game_state.to_fen());
+    assert_eq!(imported.game_state.to_pgn(&imported.tags), exported);
+}
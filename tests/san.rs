@@ -0,0 +1,97 @@
+use chess_bot::chess_logic::{GameState, MoveDescriptor, MoveParseError, Player, STARTING_FEN};
+
+mod common;
+
+use common::coordinates;
+
+fn parse(fen: &str, san: &str) -> Result<MoveDescriptor, MoveParseError> {
+    GameState::from_fen(fen).unwrap().parse_san(san)
+}
+
+#[test]
+fn parses_pieces_and_pawns() {
+    assert_eq!(parse(STARTING_FEN, "e4"), Ok(coordinates("e2e4")));
+    assert_eq!(parse(STARTING_FEN, "Nf3"), Ok(coordinates("g1f3")));
+    assert_eq!(parse(STARTING_FEN, "Ng1f3"), Ok(coordinates("g1f3")));
+}
+
+#[test]
+fn parses_pawn_captures_only_with_a_file() {
+    let fen = "4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1";
+
+    assert_eq!(parse(fen, "exd5"), Ok(coordinates("e4d5")));
+    assert_eq!(parse(fen, "d5"), Err(MoveParseError::NoMatchingMove));
+    assert_eq!(parse(fen, "e5"), Ok(coordinates("e4e5")));
+}
+
+#[test]
+fn parses_en_passant() {
+    let fen = "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1";
+
+    assert_eq!(parse(fen, "exd6"), Ok(coordinates("e5d6")));
+    assert_eq!(parse(fen, "d6"), Err(MoveParseError::NoMatchingMove));
+}
+
+#[test]
+fn parses_castling() {
+    let fen = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+
+    assert_eq!(parse(fen, "O-O"), Ok(coordinates("e1g1")));
+    assert_eq!(parse(fen, "0-0-0"), Ok(coordinates("e1c1")));
+}
+
+#[test]
+fn parses_promotions() {
+    let fen = "1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1";
+
+    assert_eq!(parse(fen, "a8=Q"), Ok(coordinates("a7a8q")));
+    assert_eq!(parse(fen, "axb8=N+"), Ok(coordinates("a7b8n")));
+    assert_eq!(parse(fen, "a8Q"), Ok(coordinates("a7a8q")));
+    assert_eq!(parse(fen, "a8"), Err(MoveParseError::NoMatchingMove));
+}
+
+#[test]
+fn requires_disambiguation() {
+    let fen = "4k3/8/8/8/8/8/4K3/R6R w - - 0 1";
+
+    assert_eq!(parse(fen, "Rd1"), Err(MoveParseError::Ambiguous));
+    assert_eq!(parse(fen, "Rad1"), Ok(coordinates("a1d1")));
+    assert_eq!(parse(fen, "Rhd1"), Ok(coordinates("h1d1")));
+}
+
+#[test]
+fn rejects_malformed_san() {
+    assert_eq!(
+        parse(STARTING_FEN, "Xe4"),
+        Err(MoveParseError::BadPiece('X'))
+    );
+    assert_eq!(parse(STARTING_FEN, "e9"), Err(MoveParseError::OffBoard));
+    assert_eq!(
+        parse(STARTING_FEN, "e5"),
+        Err(MoveParseError::NoMatchingMove)
+    );
+}
+
+#[test]
+fn formats_moves() {
+    let game_state = GameState::new(Player::White);
+    assert_eq!(game_state.to_san(coordinates("g1f3")), "Nf3");
+
+    let game_state = GameState::from_fen("4k3/8/8/8/8/8/8/R3K2R w K - 0 1").unwrap();
+    assert_eq!(game_state.to_san(coordinates("e1g1")), "O-O");
+    assert_eq!(game_state.to_san(coordinates("a1a8")), "Ra8+");
+
+    let game_state = GameState::from_fen("4k3/8/8/8/8/8/4K3/R6R w - - 0 1").unwrap();
+    assert_eq!(game_state.to_san(coordinates("a1d1")), "Rad1");
+
+    let game_state = GameState::from_fen("1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(game_state.to_san(coordinates("a7b8q")), "axb8=Q+");
+}
+
+#[test]
+fn formats_and_parses_checkmate() {
+    let game_state = GameState::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+
+    assert_eq!(game_state.to_san(coordinates("a1a8")), "Ra8#");
+    assert_eq!(game_state.parse_san("Ra8#"), Ok(coordinates("a1a8")));
+}
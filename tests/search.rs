@@ -0,0 +1,65 @@
+use chess_bot::{
+    chess_logic::GameState,
+    search::{Search, MATE_SCORE, MAX_DEPTH},
+};
+
+mod common;
+
+use common::coordinates;
+
+#[test]
+fn finds_mate_in_one() {
+    let game_state = GameState::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+    let result = Search::new().best_move(&game_state, 2).unwrap();
+
+    assert_eq!(result.best_move, coordinates("a1a8"));
+    assert_eq!(result.score, MATE_SCORE - 1.0);
+}
+
+#[test]
+fn mate_beats_the_fifty_move_rule() {
+    let game_state = GameState::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 99 80").unwrap();
+    let result = Search::new().best_move(&game_state, 2).unwrap();
+
+    assert_eq!(result.best_move, coordinates("a1a8"));
+    assert_eq!(result.score, MATE_SCORE - 1.0);
+}
+
+#[test]
+fn caps_the_search_depth() {
+    // Every reply is a dead position, so even the deepest search ends right away
+    let game_state = GameState::from_fen("k7/8/8/8/8/8/8/7K w - - 0 1").unwrap();
+    let result = Search::new().best_move(&game_state, 70).unwrap();
+
+    assert_eq!(result.depth, MAX_DEPTH);
+    assert_eq!(result.score, 0.0);
+}
+
+#[test]
+fn sees_the_recapture_at_the_horizon() {
+    // Qxd5 wins a knight at depth 1, but cxd5 takes the queen back
+    let game_state = GameState::from_fen("4k3/8/2p5/3n4/8/8/3Q4/4K3 w - - 0 1").unwrap();
+    let result = Search::new().best_move(&game_state, 1).unwrap();
+
+    assert_ne!(result.best_move, coordinates("d2d5"));
+}
+
+#[test]
+fn a_warm_search_visits_fewer_nodes() {
+    let game_state =
+        GameState::from_fen("r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4")
+            .unwrap();
+    let mut search = Search::new();
+
+    let cold = search.best_move(&game_state, 4).unwrap();
+    // The table and history from the first search should cut most of the tree
+    let warm = search.best_move(&game_state, 4).unwrap();
+
+    assert_eq!(warm.best_move, cold.best_move);
+    assert!(
+        warm.nodes < cold.nodes / 2,
+        "{} vs {}",
+        warm.nodes,
+        cold.nodes
+    );
+}